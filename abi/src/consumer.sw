@@ -0,0 +1,107 @@
+library;
+
+use std::asset_id::AssetId;
+use std::auth::msg_sender;
+use std::context::this_balance;
+use std::contract_id::ContractId;
+use std::hash::Hash;
+use std::identity::Identity;
+use std::option::Option;
+use std::revert::require;
+use std::storage::storage_key::*;
+use std::storage::storage_map::*;
+
+use ::Vrf;
+
+/// Errors logged by the consumer helpers.
+pub enum ConsumerError {
+    /// Caller is not the configured VRF contract.
+    NotVrf: (),
+    /// Contract balance doesn't cover the fee and the callback fee.
+    InsufficientBalance: (),
+    /// The identity already has a pending request.
+    RequestInProgress: (),
+}
+
+/// Reverts unless the caller is the VRF contract with the given id.
+///
+/// Pass a `configurable` id, so that tests could point it to the mock VRF.
+pub fn only_vrf(vrf_id: b256) {
+    require(
+        msg_sender()
+            .unwrap() == Identity::ContractId(ContractId::from(vrf_id)),
+        ConsumerError::NotVrf,
+    );
+}
+
+/// Returns the request fee of the VRF in the given asset.
+pub fn request_fee(vrf_id: b256, asset: AssetId) -> u64 {
+    abi(Vrf, vrf_id).get_fee(asset)
+}
+
+/// Requests randomness paying the fee (see `request_fee`) and the callback fee
+/// in the given asset from the contract balance. Returns the request number.
+///
+/// The fee is passed in, so that a caller that has already read it
+/// doesn't read it again.
+pub fn request_randomness(
+    vrf_id: b256,
+    asset: AssetId,
+    seed: b256,
+    fee: u64,
+    callback_fee: u64,
+) -> u64 {
+    let amount = fee + callback_fee;
+    require(
+        this_balance(asset) >= amount,
+        ConsumerError::InsufficientBalance,
+    );
+    abi(Vrf, vrf_id).request {
+        asset_id: asset.bits(),
+        coins: amount,
+    }(seed)
+}
+
+/// Records a pending request of the identity.
+///
+/// `owners` maps seeds to identities and `seeds` maps identities to their
+/// pending seed, so that an identity has at most one pending request.
+#[storage(read, write)]
+pub fn add_pending(
+    owners: StorageKey<StorageMap<b256, Identity>>,
+    seeds: StorageKey<StorageMap<Identity, b256>>,
+    owner: Identity,
+    seed: b256,
+) {
+    require(
+        seeds
+            .get(owner)
+            .try_read()
+            .is_none(),
+        ConsumerError::RequestInProgress,
+    );
+    owners.insert(seed, owner);
+    seeds.insert(owner, seed);
+}
+
+/// Returns the pending seed of the identity.
+#[storage(read)]
+pub fn pending_seed(seeds: StorageKey<StorageMap<Identity, b256>>, owner: Identity) -> Option<b256> {
+    seeds.get(owner).try_read()
+}
+
+/// Removes the pending request returning its owner
+/// (`None` if the seed is unknown, e.g. already taken).
+#[storage(read, write)]
+pub fn take_pending(
+    owners: StorageKey<StorageMap<b256, Identity>>,
+    seeds: StorageKey<StorageMap<Identity, b256>>,
+    seed: b256,
+) -> Option<Identity> {
+    let owner = owners.get(seed).try_read();
+    if let Some(owner) = owner {
+        let _ = owners.remove(seed);
+        let _ = seeds.remove(owner);
+    }
+    owner
+}
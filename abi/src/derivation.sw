@@ -0,0 +1,128 @@
+library;
+
+use std::b512::B512;
+use std::hash::*;
+use std::revert::require;
+
+/// Errors logged by the derivation helpers.
+pub enum DerivationError {
+    /// `min` must be less than `max`.
+    EmptyRange: (),
+    /// Denominator must not be zero.
+    ZeroDenominator: (),
+}
+
+/// Returns a sub-value of the randomness keyed by the index:
+/// `sha256((randomness, index))`.
+///
+/// Sub-values of distinct indices are independent. Equals to
+/// `orao_fuel_vrf::derive::sub_value(randomness, &index.to_be_bytes())` in Rust.
+pub fn sub_value(randomness: B512, index: u64) -> b256 {
+    let bits = randomness.bits();
+    sha256((bits[0], bits[1], index))
+}
+
+/// Returns `count` independent sub-values keyed by the index:
+/// `sha256((sub_value(randomness, index), counter))` for `counter` in `0..count`.
+///
+/// Equals to
+/// `orao_fuel_vrf::derive::sub_values(randomness, &index.to_be_bytes(), count)` in Rust.
+pub fn sub_values(randomness: B512, index: u64, count: u64) -> Vec<b256> {
+    let key = sub_value(randomness, index);
+    let mut values = Vec::with_capacity(count);
+    let mut counter = 0;
+    while counter < count {
+        values.push(sha256((key, counter)));
+        counter += 1;
+    }
+    values
+}
+
+/// Splits the value into big-endian words.
+fn words(value: b256) -> [u64; 4] {
+    asm(r1: value) {
+        r1: [u64; 4]
+    }
+}
+
+/// Returns a uniformly distributed integer in `[min, max)` keyed by the index.
+///
+/// Words of `sha256((sub_value(randomness, index), counter))` for `counter = 0, 1, …`
+/// are taken in order, values below `2^64 mod (max - min)` are rejected to avoid
+/// the modulo bias. Equals to
+/// `orao_fuel_vrf::derive::uniform(randomness, &index.to_be_bytes(), min..max)` in Rust.
+pub fn uniform(randomness: B512, index: u64, min: u64, max: u64) -> u64 {
+    require(min < max, DerivationError::EmptyRange);
+    let n = max - min;
+    let rejected = (u64::max() % n + 1) % n;
+
+    let key = sub_value(randomness, index);
+    let mut counter = 0;
+    let mut value = 0;
+    let mut found = false;
+    while !found {
+        let block = words(sha256((key, counter)));
+        let mut i = 0;
+        while i < 4 && !found {
+            if block[i] >= rejected {
+                value = block[i];
+                found = true;
+            }
+            i += 1;
+        }
+        counter += 1;
+    }
+    min + value % n
+}
+
+/// Returns `true` with the probability of `numerator / denominator`.
+pub fn chance(randomness: B512, index: u64, numerator: u64, denominator: u64) -> bool {
+    require(denominator > 0, DerivationError::ZeroDenominator);
+    uniform(randomness, index, 0, denominator) < numerator
+}
+
+/// Rolls a die with the given number of sides, returns a value in `[1, sides]`.
+pub fn roll_die(randomness: B512, index: u64, sides: u64) -> u64 {
+    uniform(randomness, index, 1, sides + 1)
+}
+
+// Known-answer vectors shared with the Rust SDK (`orao_fuel_vrf::derive` tests).
+
+fn test_randomness() -> B512 {
+    B512::from((
+        0x0101010101010101010101010101010101010101010101010101010101010101,
+        0x0202020202020202020202020202020202020202020202020202020202020202,
+    ))
+}
+
+#[test]
+fn test_sub_value() {
+    assert_eq(
+        sub_value(test_randomness(), 0),
+        0xde3850ed7f23f5e928a285518e5eafe239a17b1a967a4b1480899ddc3f967b88,
+    );
+    assert_eq(
+        sub_value(test_randomness(), 1),
+        0xb42fb843fa20f30cbd53fed8300077633f936e486f2007a20e478bbe0d0d8e80,
+    );
+}
+
+#[test]
+fn test_sub_values() {
+    let values = sub_values(test_randomness(), 0, 2);
+    assert_eq(values.len(), 2);
+    assert_eq(
+        values.get(0).unwrap(),
+        0xeaee4665809783a0cc8a62e1397fc1de106e6b310ffcf8c7ff6d75ca7534dfbd,
+    );
+    assert_eq(
+        values.get(1).unwrap(),
+        0xb4491e30dcd1c16fdaa3e317f8fc132996d51f5bdc87627b6e0eb603f8cfcdce,
+    );
+}
+
+#[test]
+fn test_uniform() {
+    assert_eq(uniform(test_randomness(), 0, 1, 7), 5);
+    assert_eq(uniform(test_randomness(), 1, 0, 1000), 604);
+}
@@ -1,5 +1,7 @@
 library;
 
+pub mod consumer;
+pub mod derivation;
 pub mod randomness;
 
 use std::asset::*;
@@ -0,0 +1,291 @@
+//! Fulfillment authority daemon.
+
+use std::{future::Future, io, path::Path, time::Duration};
+
+use anyhow::Context;
+use fuels::{
+    prelude::*,
+    types::{Bits256, BlockHeight, Bytes32},
+};
+use futures::{StreamExt, TryStreamExt};
+use orao_fuel_vrf::{fulfiller::Fulfiller, history::StateFilter, Event, Vrf};
+use tokio::time::sleep;
+
+/// Number of attempts to respond to a request before it is skipped.
+const MAX_ATTEMPTS: u32 = 5;
+/// Delay before the first retry, doubled after each attempt.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Responds to requests until an unrecoverable error occurs.
+///
+/// Events are tailed from the block stored in the `cursor` file or from the `from` block,
+/// if there is no cursor yet. The cursor is the block of the last processed event –
+/// it is delivered again after a restart, which is fine since responses are only
+/// submitted for requests that await them.
+///
+/// Without a cursor, unfulfilled requests of the whole history are responded to first,
+/// so that requests performed before `from` are not left behind (pass the current
+/// block as `from` to not miss requests performed during the scan).
+///
+/// Failed responses are retried [`MAX_ATTEMPTS`] times (contract reverts aren't retried),
+/// then the request is logged and skipped.
+pub async fn run<A: Account>(
+    instance: &Vrf<A>,
+    fulfiller: &Fulfiller,
+    cursor: &Path,
+    from: BlockHeight,
+) -> anyhow::Result<()> {
+    let status = instance.get_status().await?;
+    anyhow::ensure!(
+        status
+            .fulfillment_authorities
+            .contains(&fulfiller.address()),
+        "{} is not a configured fulfillment authority",
+        fulfiller.address()
+    );
+
+    let mut height = match load_cursor(cursor)? {
+        Some(height) => height,
+        None => {
+            respond_unfulfilled(instance, fulfiller).await?;
+            from
+        }
+    };
+    eprintln!(
+        "Fulfilling requests as {} from block {height}",
+        fulfiller.address()
+    );
+    loop {
+        let events = instance.events(height);
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            match event {
+                Ok((event_height, _, event)) => {
+                    handle(instance, fulfiller, &event).await;
+                    if event_height != height {
+                        height = event_height;
+                        store_cursor(cursor, height)?;
+                    }
+                }
+                Err(e) => {
+                    // resumed from the cursor
+                    eprintln!("Error: {e:#}");
+                    break;
+                }
+            }
+        }
+        sleep(orao_fuel_vrf::events::DEFAULT_POLL_INTERVAL).await;
+    }
+}
+
+/// Responds to all the unfulfilled requests that await this authority.
+async fn respond_unfulfilled<A: Account>(
+    instance: &Vrf<A>,
+    fulfiller: &Fulfiller,
+) -> anyhow::Result<()> {
+    eprintln!("Looking for unfulfilled requests");
+    let requests = instance
+        .requests()
+        .state(StateFilter::Unfulfilled)
+        .stream()
+        .try_filter(|request| futures::future::ready(fulfiller.is_pending(request)));
+    futures::pin_mut!(requests);
+    while let Some(request) = requests
+        .try_next()
+        .await
+        .context("Unable to fetch requests")?
+    {
+        respond(instance, fulfiller, request.seed).await;
+    }
+    Ok(())
+}
+
+async fn handle<A: Account>(instance: &Vrf<A>, fulfiller: &Fulfiller, event: &Event) {
+    // a reset request awaits responses again
+    let seed = match event {
+        Event::Request(request) => request.seed,
+        Event::Reset(reset) => {
+            eprintln!("Request {} is reset", Bytes32::new(reset.seed.0));
+            reset.seed
+        }
+        _ => return,
+    };
+    respond(instance, fulfiller, seed).await;
+}
+
+/// Responds to the request logging the outcome.
+async fn respond<A: Account>(instance: &Vrf<A>, fulfiller: &Fulfiller, raw_seed: Bits256) {
+    let seed = Bytes32::new(raw_seed.0);
+    match retry(RETRY_DELAY, || fulfiller.respond(instance, raw_seed)).await {
+        Ok(Some(response)) => match response.tx_id {
+            Some(tx_id) => eprintln!("Responded to {seed} in {tx_id}"),
+            None => eprintln!("Responded to {seed}"),
+        },
+        Ok(None) => eprintln!("Skipped {seed}: already responded or fulfilled"),
+        Err(e) => eprintln!("Error: unable to respond to {seed}, skipped: {e:#}"),
+    }
+}
+
+/// Calls `f` until it succeeds, at most [`MAX_ATTEMPTS`] times, starting with
+/// the given delay between attempts. Contract reverts are returned immediately.
+async fn retry<T, F, Fut>(mut delay: Duration, mut f: F) -> orao_fuel_vrf::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = orao_fuel_vrf::Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match f().await {
+            Err(e) if attempt < MAX_ATTEMPTS && !is_revert(&e) => {
+                eprintln!("Error: {e:#} (attempt {attempt}/{MAX_ATTEMPTS})");
+                sleep(delay).await;
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+fn is_revert(error: &orao_fuel_vrf::Error) -> bool {
+    matches!(
+        error,
+        orao_fuel_vrf::Error::Contract(_)
+            | orao_fuel_vrf::Error::Access(_)
+            | orao_fuel_vrf::Error::Initialization(_)
+    )
+}
+
+fn load_cursor(path: &Path) -> anyhow::Result<Option<BlockHeight>> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => {
+            let height = contents
+                .trim()
+                .parse::<u32>()
+                .with_context(|| format!("Invalid cursor file {}", path.display()))?;
+            Ok(Some(height.into()))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e).with_context(|| format!("Unable to read {}", path.display())),
+    }
+}
+
+/// Stores the cursor atomically, so that it is never left half-written.
+fn store_cursor(path: &Path, height: BlockHeight) -> anyhow::Result<()> {
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, format!("{height}\n"))
+        .and_then(|()| std::fs::rename(&tmp, path))
+        .with_context(|| format!("Unable to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, path::PathBuf, str::FromStr};
+
+    use fuels::crypto::SecretKey;
+    use orao_fuel_vrf::{
+        testing::{TestConfig, TestEnv},
+        wait::WaitOptions,
+        ContractError, Error,
+    };
+
+    use super::*;
+
+    fn cursor_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "orao-fuel-vrf-{name}-{}.cursor",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    #[tokio::test]
+    async fn retries() {
+        let attempts = Cell::new(0);
+        let result = retry(Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(Error::Cancelled) }
+        })
+        .await;
+        assert!(matches!(result, Err(Error::Cancelled)));
+        assert_eq!(attempts.get(), MAX_ATTEMPTS);
+
+        // reverts are not retried
+        attempts.set(0);
+        let result = retry(Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(ContractError::UnknownRequest.into()) }
+        })
+        .await;
+        assert!(matches!(
+            result,
+            Err(Error::Contract(ContractError::UnknownRequest))
+        ));
+        assert_eq!(attempts.get(), 1);
+
+        attempts.set(0);
+        let result = retry(Duration::ZERO, || {
+            attempts.set(attempts.get() + 1);
+            let attempt = attempts.get();
+            async move {
+                if attempt < 3 {
+                    Err(Error::Cancelled)
+                } else {
+                    Ok(attempt)
+                }
+            }
+        })
+        .await;
+        assert!(matches!(result, Ok(3)));
+    }
+
+    #[tokio::test]
+    async fn unknown_authority() -> anyhow::Result<()> {
+        let env = TestEnv::launch_with(TestConfig::default()).await?;
+        let fulfiller = Fulfiller::new(SecretKey::from_str(&"07".repeat(32))?);
+        let cursor = cursor_path("unknown-authority");
+        let error = run(&env.vrf, &fulfiller, &cursor, 0.into())
+            .await
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("is not a configured fulfillment authority"),
+            "{error}"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn fulfills() -> anyhow::Result<()> {
+        let env = TestEnv::launch_with(TestConfig {
+            num_authorities: 1,
+            ..TestConfig::default()
+        })
+        .await?;
+        // performed before the start block, so only found by the initial scan
+        let before = Bits256([1; 32]);
+        env.request(before).await?;
+        let from = env.provider.latest_block_height().await? + 1;
+        let cursor = cursor_path("fulfills");
+
+        let requests = async {
+            let after = Bits256([2; 32]);
+            env.request(after).await?;
+            for seed in [before, after] {
+                env.vrf.wait_fulfilled(seed, WaitOptions::default()).await?;
+            }
+            anyhow::Ok(())
+        };
+        tokio::select! {
+            result = run(&env.vrf, &env.fulfillers[0], &cursor, from.into()) => {
+                panic!("daemon stopped: {result:?}")
+            }
+            result = tokio::time::timeout(Duration::from_secs(60), requests) => result??,
+        }
+        assert!(load_cursor(&cursor)?.is_some());
+        std::fs::remove_file(&cursor)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,505 @@
+use std::{path::PathBuf, str::FromStr, time::Duration};
+
+use anyhow::Context;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use fuels::{
+    crypto::SecretKey,
+    prelude::*,
+    types::{Bits256, BlockHeight, Bytes32, Identity},
+};
+use futures::{StreamExt, TryStreamExt};
+use orao_fuel_vrf::{
+    fulfiller::Fulfiller,
+    history::StateFilter,
+    wait::{RequestKey, WaitOptions},
+    Network, Vrf,
+};
+
+use crate::output::{FulfilledOutput, ProxyOutput, TxOutput};
+
+mod fulfiller;
+#[cfg(feature = "metrics")]
+mod metrics;
+mod output;
+mod wallet;
+
+/// ORAO Fuel VRF command-line tool.
+#[derive(Debug, Parser)]
+#[command(version)]
+pub struct Args {
+    /// Network to connect to.
+    #[arg(long, global = true, value_enum, default_value_t = NetworkArg::Testnet)]
+    pub network: NetworkArg,
+
+    /// Id of a published VRF proxy contract (overrides the network preset).
+    #[arg(long, global = true)]
+    pub contract_id: Option<ContractId>,
+
+    /// Encrypted keystore path.
+    #[arg(long, global = true, default_value = "~/.fuel/wallets/.wallet")]
+    pub wallet: String,
+
+    /// Wallet password (program will ask for it interactively if missing).
+    #[arg(
+        long,
+        global = true,
+        env = "FUEL_WALLET_PASSWORD",
+        hide_env_values = true
+    )]
+    pub wallet_password: Option<String>,
+
+    /// Hex-encoded private key to use instead of the keystore.
+    #[arg(
+        long,
+        global = true,
+        env = orao_fuel_vrf::wallet::PRIVATE_KEY_ENV,
+        hide_env_values = true
+    )]
+    pub private_key: Option<String>,
+
+    /// Wallet account index.
+    #[arg(long, global = true, default_value_t = 0_usize)]
+    pub account_index: usize,
+
+    /// Fuel node endpoint to connect to (overrides the network preset).
+    #[arg(long, global = true)]
+    pub endpoint: Option<String>,
+
+    /// Print the output as JSON.
+    #[arg(long, global = true)]
+    pub json: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+impl Args {
+    fn network(&self) -> anyhow::Result<Network> {
+        let network = match self.network {
+            NetworkArg::Mainnet => Network::Mainnet,
+            NetworkArg::Testnet => Network::Testnet,
+            NetworkArg::Local => Network::Local {
+                contract_id: self.contract_id.ok_or_else(|| {
+                    anyhow::anyhow!("--contract-id is required for local network")
+                })?,
+            },
+        };
+        if self.contract_id.is_none() && self.endpoint.is_none() {
+            return Ok(network);
+        }
+        Ok(Network::Custom {
+            endpoint: self
+                .endpoint
+                .clone()
+                .unwrap_or_else(|| network.endpoint().to_string()),
+            contract_id: self.contract_id.unwrap_or_else(|| network.contract_id()),
+            chain_id: network.chain_id(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum NetworkArg {
+    Mainnet,
+    Testnet,
+    Local,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Prints the VRF configuration and balances.
+    Status,
+    /// Requests randomness.
+    Request {
+        /// Request seed.
+        ///
+        /// Will generate random unused seed, if not given.
+        #[arg(long)]
+        seed: Option<Bytes32>,
+        /// Pay with the additional asset, if configured.
+        #[arg(long)]
+        prefer_additional_asset: bool,
+        /// Amount paid on top of the fee to cover the consumer callback.
+        #[arg(long, default_value_t = 0)]
+        callback_fee: u64,
+        /// Wait for the request to be fulfilled.
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Waits for a request to be fulfilled.
+    Wait {
+        /// Request seed or number.
+        request: RequestArg,
+        /// Give up after the given number of seconds.
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Prints a request.
+    Get {
+        /// Request seed or number.
+        request: RequestArg,
+    },
+    /// Prints performed requests.
+    History {
+        /// Start from the latest request.
+        #[arg(long)]
+        descending: bool,
+        /// Only requests of the given address.
+        #[arg(long, conflicts_with = "contract")]
+        address: Option<Address>,
+        /// Only requests of the given contract.
+        #[arg(long)]
+        contract: Option<ContractId>,
+        /// Only requests in the given state.
+        #[arg(long)]
+        state: Option<StateArg>,
+        /// Maximum number of requests to print.
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Configures the VRF (authority only).
+    Configure {
+        #[command(subcommand)]
+        command: ConfigureCommand,
+    },
+    /// Withdraws collected fees (authority only).
+    Withdraw {
+        /// Amount to withdraw.
+        amount: u64,
+        /// Asset to withdraw (defaults to the base asset).
+        #[arg(long)]
+        asset: Option<AssetId>,
+        /// Recipient address (defaults to the wallet address).
+        #[arg(long)]
+        recipient: Option<Address>,
+    },
+    /// Manages the VRF proxy.
+    Proxy {
+        #[command(subcommand)]
+        command: ProxyCommand,
+    },
+    /// Runs a fulfillment authority that responds to requests (never returns).
+    Fulfiller {
+        /// Hex-encoded secret key of the fulfillment authority.
+        #[arg(long, env = "ORAO_VRF_AUTHORITY_KEY", hide_env_values = true)]
+        authority_key: String,
+        /// File to persist the last processed block in.
+        #[arg(long, default_value = "orao-fuel-vrf-fulfiller.cursor")]
+        cursor: PathBuf,
+        /// Block to start from if there is no cursor yet (defaults to the latest block),
+        /// unfulfilled requests of earlier blocks are responded to on start.
+        #[arg(long)]
+        from_block: Option<u32>,
+    },
+    /// Serves Prometheus metrics.
+    #[cfg(feature = "metrics")]
+    Metrics {
+        /// Address to serve `/metrics` on.
+        #[arg(long, default_value = "127.0.0.1:9100")]
+        listen: std::net::SocketAddr,
+        /// Status polling interval in seconds.
+        #[arg(long, default_value_t = 15)]
+        interval: u64,
+        /// Block to start tracking requests from (defaults to the latest block).
+        #[arg(long)]
+        from_block: Option<u32>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ConfigureCommand {
+    /// Sets the authority, the base asset fee and fulfillment authorities.
+    Authorities {
+        /// New VRF authority address.
+        #[arg(long)]
+        authority: Address,
+        /// Base asset fee.
+        #[arg(long)]
+        fee: u64,
+        /// Fulfillment authority address (repeat for each authority).
+        #[arg(long = "fulfillment-authority", required = true)]
+        fulfillment_authorities: Vec<Address>,
+    },
+    /// Sets the additional asset and its fee.
+    Asset {
+        asset: AssetId,
+        /// Fee (use 0 with the base asset to disable the additional asset).
+        #[arg(long)]
+        fee: u64,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProxyCommand {
+    /// Prints the proxy target and owner.
+    Show,
+    /// Switches the proxy to a new VRF contract (proxy owner only).
+    Upgrade { target: ContractId },
+    /// Transfers the proxy ownership (proxy owner only).
+    TransferOwnership { new_owner: Address },
+}
+
+impl Command {
+    fn needs_wallet(&self) -> bool {
+        matches!(
+            self,
+            Command::Request { .. }
+                | Command::Fulfiller { .. }
+                | Command::Configure { .. }
+                | Command::Withdraw { .. }
+                | Command::Proxy {
+                    command: ProxyCommand::Upgrade { .. } | ProxyCommand::TransferOwnership { .. }
+                }
+        )
+    }
+}
+
+/// Request number or seed.
+#[derive(Debug, Clone, Copy)]
+pub struct RequestArg(RequestKey);
+
+impl FromStr for RequestArg {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        if let Ok(num) = s.parse::<u64>() {
+            return Ok(Self(RequestKey::Num(num)));
+        }
+        Bytes32::from_str(s)
+            .map(|seed| Self(RequestKey::Seed(Bits256(*seed))))
+            .map_err(|_| "expected a request number or a 32-byte hex seed".to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum StateArg {
+    Fulfilled,
+    Unfulfilled,
+}
+
+impl From<StateArg> for StateFilter {
+    fn from(state: StateArg) -> Self {
+        match state {
+            StateArg::Fulfilled => StateFilter::Fulfilled,
+            StateArg::Unfulfilled => StateFilter::Unfulfilled,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args = Args::parse();
+
+    let network = args.network()?;
+    eprintln!("Using node address: {}", network.endpoint());
+    let provider = network.connect().await?;
+    eprintln!("Using contract address: {}\n", network.contract_id());
+
+    if args.command.needs_wallet() {
+        let wallet = wallet::load(&args, provider)?;
+        let instance = Vrf::try_new(network.contract_id(), wallet).await?;
+        run(&args, &instance).await
+    } else {
+        let instance = Vrf::try_new(network.contract_id(), wallet::read_only(provider)).await?;
+        run(&args, &instance).await
+    }
+}
+
+async fn run<A: Account>(args: &Args, instance: &Vrf<A>) -> anyhow::Result<()> {
+    match &args.command {
+        Command::Status => {
+            output::print(args.json, &instance.get_status().await?);
+        }
+        Command::Request {
+            seed,
+            prefer_additional_asset,
+            callback_fee,
+            wait,
+        } => {
+            let seed = match seed {
+                Some(seed) => Bits256(**seed),
+                None => instance.fresh_seed().await?,
+            };
+            let mut builder = instance.request_builder(seed).callback_fee(*callback_fee);
+            if *prefer_additional_asset {
+                builder = builder.prefer_additional_asset();
+            }
+            let receipt = builder.send().await?;
+            if *wait {
+                eprintln!("Waiting for randomness to be fulfilled..");
+                let fulfilled = instance
+                    .wait_fulfilled(receipt.num, WaitOptions::default())
+                    .await?;
+                output::print(
+                    args.json,
+                    &FulfilledOutput {
+                        seed,
+                        fulfilled: &fulfilled,
+                    },
+                );
+            } else {
+                output::print(args.json, &receipt);
+            }
+        }
+        Command::Wait { request, timeout } => {
+            let mut options = WaitOptions::default();
+            if let Some(timeout) = timeout {
+                options = options.with_timeout(Duration::from_secs(*timeout));
+            }
+            let fulfilled = instance.wait_fulfilled(request.0, options).await?;
+            let seed = match request.0 {
+                RequestKey::Seed(seed) => seed,
+                RequestKey::Num(num) => get(instance, RequestKey::Num(num)).await?.seed,
+            };
+            output::print(
+                args.json,
+                &FulfilledOutput {
+                    seed,
+                    fulfilled: &fulfilled,
+                },
+            );
+        }
+        Command::Get { request } => {
+            output::print(args.json, &get(instance, request.0).await?);
+        }
+        Command::History {
+            descending,
+            address,
+            contract,
+            state,
+            limit,
+        } => {
+            let mut query = instance.requests();
+            if *descending {
+                query = query.descending();
+            }
+            if let Some(address) = address {
+                query = query.sender(Identity::Address(*address));
+            }
+            if let Some(contract) = contract {
+                query = query.sender(Identity::ContractId(*contract));
+            }
+            if let Some(state) = state {
+                query = query.state((*state).into());
+            }
+            let requests: Vec<_> = query.stream().take(*limit).try_collect().await?;
+            output::print(args.json, &requests);
+        }
+        Command::Configure { command } => {
+            let response = match command {
+                ConfigureCommand::Authorities {
+                    authority,
+                    fee,
+                    fulfillment_authorities,
+                } => {
+                    instance
+                        .configure(
+                            Identity::Address(*authority),
+                            *fee,
+                            fulfillment_authorities.clone(),
+                        )
+                        .await?
+                }
+                ConfigureCommand::Asset { asset, fee } => {
+                    instance.configure_asset(*asset, *fee).await?
+                }
+            };
+            output::print(args.json, &TxOutput(response.tx_id));
+        }
+        Command::Withdraw {
+            amount,
+            asset,
+            recipient,
+        } => {
+            let asset = match asset {
+                Some(asset) => *asset,
+                None => instance.base_asset_id().await?,
+            };
+            let recipient = recipient.unwrap_or_else(|| instance.abi.account().address().into());
+            let response = instance.withdraw_fees(asset, *amount, recipient).await?;
+            output::print(args.json, &TxOutput(response.tx_id));
+        }
+        Command::Proxy { command } => {
+            let proxy = instance.proxy();
+            match command {
+                ProxyCommand::Show => {
+                    let output = ProxyOutput {
+                        contract_id: proxy.contract_id,
+                        target: proxy.target().await?,
+                        owner: proxy.owner().await?,
+                    };
+                    output::print(args.json, &output);
+                }
+                ProxyCommand::Upgrade { target } => {
+                    let response = proxy.upgrade_to(*target).await?;
+                    output::print(args.json, &TxOutput(response.tx_id));
+                }
+                ProxyCommand::TransferOwnership { new_owner } => {
+                    let response = proxy.transfer_ownership(*new_owner).await?;
+                    output::print(args.json, &TxOutput(response.tx_id));
+                }
+            }
+        }
+        Command::Fulfiller {
+            authority_key,
+            cursor,
+            from_block,
+        } => {
+            let secret_key =
+                SecretKey::from_str(authority_key.trim()).context("Invalid authority key")?;
+            fulfiller::run(
+                instance,
+                &Fulfiller::new(secret_key),
+                cursor,
+                start_block(instance, *from_block).await?,
+            )
+            .await?;
+        }
+        #[cfg(feature = "metrics")]
+        Command::Metrics {
+            listen,
+            interval,
+            from_block,
+        } => {
+            metrics::run(
+                instance,
+                *listen,
+                Duration::from_secs(*interval),
+                start_block(instance, *from_block).await?,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Returns the given block or the latest one.
+async fn start_block<A: Account>(
+    instance: &Vrf<A>,
+    from_block: Option<u32>,
+) -> anyhow::Result<BlockHeight> {
+    let height = match from_block {
+        Some(height) => height,
+        None => {
+            instance
+                .abi
+                .account()
+                .try_provider()?
+                .latest_block_height()
+                .await?
+        }
+    };
+    Ok(height.into())
+}
+
+async fn get<A: Account>(
+    instance: &Vrf<A>,
+    request: RequestKey,
+) -> anyhow::Result<orao_fuel_vrf::Randomness> {
+    let randomness = match request {
+        RequestKey::Num(num) => instance.get_request_by_num(num).await?,
+        RequestKey::Seed(seed) => instance.get_request_by_seed(seed).await?,
+    };
+    randomness.ok_or_else(|| anyhow::anyhow!("Request not found"))
+}
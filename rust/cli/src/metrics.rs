@@ -0,0 +1,461 @@
+//! Prometheus metrics exporter (requires the `metrics` feature).
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{atomic::AtomicU64, Arc},
+    time::Duration,
+};
+
+use axum::{http::header::CONTENT_TYPE, routing::get, Router};
+use fuels::{prelude::*, types::BlockHeight};
+use futures::StreamExt;
+use orao_fuel_vrf::{
+    events::{RequestTracker, RequestUpdate},
+    Event, Status, Vrf, MAX_AUTHORITIES,
+};
+use prometheus_client::{
+    encoding::text::encode,
+    metrics::{
+        family::Family,
+        gauge::Gauge,
+        histogram::{exponential_buckets, linear_buckets, Histogram},
+    },
+    registry::Registry,
+};
+use tokio::{net::TcpListener, time::sleep};
+
+type AssetLabels = Vec<(&'static str, String)>;
+
+#[derive(Debug)]
+struct Metrics {
+    num_requests: Gauge,
+    pending_requests: Gauge,
+    fulfillment_authorities: Gauge,
+    fee: Family<AssetLabels, Gauge<f64, AtomicU64>>,
+    balance: Family<AssetLabels, Gauge<f64, AtomicU64>>,
+    latency_blocks: Histogram,
+    latency_seconds: Histogram,
+    responses: Histogram,
+}
+
+impl Metrics {
+    fn register(registry: &mut Registry) -> Self {
+        let metrics = Self {
+            num_requests: Gauge::default(),
+            pending_requests: Gauge::default(),
+            fulfillment_authorities: Gauge::default(),
+            fee: Family::default(),
+            balance: Family::default(),
+            latency_blocks: Histogram::new(exponential_buckets(1.0, 2.0, 12)),
+            latency_seconds: Histogram::new(exponential_buckets(0.5, 2.0, 12)),
+            responses: Histogram::new(linear_buckets(1.0, 1.0, MAX_AUTHORITIES as u16)),
+        };
+        registry.register(
+            "num_requests",
+            "Total number of requests",
+            metrics.num_requests.clone(),
+        );
+        registry.register(
+            "pending_requests",
+            "Number of observed requests that are not yet fulfilled",
+            metrics.pending_requests.clone(),
+        );
+        registry.register(
+            "fulfillment_authorities",
+            "Number of fulfillment authorities",
+            metrics.fulfillment_authorities.clone(),
+        );
+        registry.register("fee", "Request fee per asset", metrics.fee.clone());
+        registry.register(
+            "balance",
+            "Collected fees per asset",
+            metrics.balance.clone(),
+        );
+        registry.register(
+            "fulfillment_latency_blocks",
+            "Number of blocks between the request and its fulfillment",
+            metrics.latency_blocks.clone(),
+        );
+        registry.register(
+            "fulfillment_latency_seconds",
+            "Time between the request and its fulfillment",
+            metrics.latency_seconds.clone(),
+        );
+        registry.register(
+            "responses_per_request",
+            "Number of authority responses observed before the fulfillment",
+            metrics.responses.clone(),
+        );
+        metrics
+    }
+
+    fn update_status(&self, status: &Status, base_asset_id: AssetId) {
+        self.num_requests.set(status.num_requests as i64);
+        self.fulfillment_authorities
+            .set(status.fulfillment_authorities.len() as i64);
+        let assets =
+            std::iter::once((base_asset_id, status.base_asset)).chain(status.additional_asset);
+        for (asset, asset_status) in assets {
+            let labels = vec![("asset", asset.to_string())];
+            self.fee.get_or_create(&labels).set(asset_status.fee as f64);
+            self.balance
+                .get_or_create(&labels)
+                .set(asset_status.balance as f64);
+        }
+    }
+}
+
+/// Latency and the number of responses of a fulfilled request.
+#[derive(Debug, Clone, PartialEq)]
+struct Fulfillment {
+    blocks: u32,
+    seconds: Option<f64>,
+    responses: usize,
+}
+
+/// Bookkeeping of the observed requests on top of [`RequestTracker`].
+#[derive(Debug, Default)]
+struct Requests {
+    tracker: RequestTracker,
+    /// Block and its timestamp in milliseconds per observed request.
+    requested: HashMap<[u8; 32], (BlockHeight, Option<i64>)>,
+}
+
+impl Requests {
+    /// Applies the event of the block with the given timestamp in milliseconds
+    /// (only needed for requests and fulfillments).
+    ///
+    /// Returns the fulfillment of a request observed since its start.
+    fn apply(
+        &mut self,
+        height: BlockHeight,
+        time: Option<i64>,
+        event: &Event,
+    ) -> Option<Fulfillment> {
+        let responses = match event {
+            Event::Fulfill(fulfill) => self.tracker.get(fulfill.seed).map(|x| x.responses.len()),
+            _ => None,
+        };
+        match (self.tracker.apply(event)?, event) {
+            (RequestUpdate::Requested, Event::Request(request)) => {
+                self.requested
+                    .entry(request.seed.0)
+                    .or_insert((height, time));
+                None
+            }
+            (RequestUpdate::Fulfilled, Event::Fulfill(fulfill)) => {
+                let (start, start_time) = self.requested.remove(&fulfill.seed.0)?;
+                Some(Fulfillment {
+                    blocks: *height - *start,
+                    seconds: start_time
+                        .zip(time)
+                        .map(|(start, end)| (end - start) as f64 / 1000.0),
+                    responses: responses.unwrap_or_default(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Number of observed requests that are not yet fulfilled.
+    fn pending(&self) -> usize {
+        self.tracker.pending().count()
+    }
+}
+
+/// Position in the event stream.
+///
+/// Events of a block may span several pages of the stream, so it is resumed
+/// from the block of the last processed event skipping the processed ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    height: BlockHeight,
+    /// Number of processed events of the block.
+    processed: usize,
+    /// Number of processed events left to skip after resuming.
+    skip: usize,
+}
+
+impl Position {
+    fn new(from: BlockHeight) -> Self {
+        Self {
+            height: from,
+            processed: 0,
+            skip: 0,
+        }
+    }
+
+    /// Returns the block to resume the stream from.
+    fn resume(&mut self) -> BlockHeight {
+        self.skip = self.processed;
+        self.height
+    }
+
+    /// Advances the position to the event of the given block.
+    ///
+    /// Returns `false` if the event is already processed.
+    fn advance(&mut self, height: BlockHeight) -> bool {
+        if height != self.height {
+            *self = Self {
+                height,
+                processed: 1,
+                skip: 0,
+            };
+        } else if self.skip > 0 {
+            self.skip -= 1;
+            return false;
+        } else {
+            self.processed += 1;
+        }
+        true
+    }
+}
+
+/// Serves metrics on `listen` until an error occurs.
+///
+/// Request latencies are only known for requests performed at or after the `from` block.
+pub async fn run<A: Account>(
+    instance: &Vrf<A>,
+    listen: SocketAddr,
+    interval: Duration,
+    from: BlockHeight,
+) -> anyhow::Result<()> {
+    let mut registry = Registry::with_prefix("orao_vrf");
+    let metrics = Metrics::register(&mut registry);
+    let registry = Arc::new(registry);
+
+    let app = Router::new().route(
+        "/metrics",
+        get(move || async move {
+            let mut body = String::new();
+            encode(&mut body, &registry).expect("writing to a string never fails");
+            (
+                [(
+                    CONTENT_TYPE,
+                    "application/openmetrics-text; version=1.0.0; charset=utf-8",
+                )],
+                body,
+            )
+        }),
+    );
+    let listener = TcpListener::bind(listen).await?;
+    eprintln!("Serving metrics on http://{listen}/metrics");
+
+    tokio::select! {
+        result = axum::serve(listener, app) => result?,
+        result = poll_status(instance, &metrics, interval) => result?,
+        result = track_events(instance, &metrics, from) => result?,
+    }
+    Ok(())
+}
+
+async fn poll_status<A: Account>(
+    instance: &Vrf<A>,
+    metrics: &Metrics,
+    interval: Duration,
+) -> anyhow::Result<()> {
+    let base_asset_id = instance.base_asset_id().await?;
+    loop {
+        match instance.get_status().await {
+            Ok(status) => metrics.update_status(&status, base_asset_id),
+            Err(e) => eprintln!("Unable to get status: {e}"),
+        }
+        sleep(interval).await;
+    }
+}
+
+async fn track_events<A: Account>(
+    instance: &Vrf<A>,
+    metrics: &Metrics,
+    from: BlockHeight,
+) -> anyhow::Result<()> {
+    let provider = instance.abi.account().try_provider()?.clone();
+    let block_time = |height: BlockHeight| {
+        let provider = provider.clone();
+        async move {
+            provider
+                .block_by_height(height)
+                .await
+                .ok()
+                .flatten()
+                .and_then(|block| block.header.time)
+                .map(|time| time.timestamp_millis())
+        }
+    };
+
+    let mut requests = Requests::default();
+    let mut position = Position::new(from);
+    loop {
+        let events = instance.events(position.resume());
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            let (event_height, _, event) = match event {
+                Ok(event) => event,
+                Err(e) => {
+                    eprintln!("Event stream failed: {e}");
+                    break;
+                }
+            };
+            if !position.advance(event_height) {
+                continue;
+            }
+            let time = match event {
+                Event::Request(_) | Event::Fulfill(_) => block_time(event_height).await,
+                _ => None,
+            };
+            if let Some(fulfillment) = requests.apply(event_height, time, &event) {
+                metrics.latency_blocks.observe(fulfillment.blocks as f64);
+                if let Some(seconds) = fulfillment.seconds {
+                    metrics.latency_seconds.observe(seconds);
+                }
+                metrics.responses.observe(fulfillment.responses as f64);
+            }
+            metrics.pending_requests.set(requests.pending() as i64);
+        }
+        sleep(orao_fuel_vrf::events::DEFAULT_POLL_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::types::{Bits256, Identity, B512};
+    use orao_fuel_vrf::{Fulfill, Request, Reset, Response};
+
+    use super::*;
+
+    const SEED: Bits256 = Bits256([1; 32]);
+
+    const RANDOMNESS: B512 = B512 {
+        bytes: [Bits256([2; 32]), Bits256([3; 32])],
+    };
+
+    fn request(seed: Bits256) -> Event {
+        Event::Request(Request {
+            seed,
+            client: Identity::Address(Address::new([9; 32])),
+            no: 0,
+        })
+    }
+
+    fn response(seed: Bits256, authority: u8) -> Event {
+        Event::Response(Response {
+            seed,
+            authority: Address::new([authority; 32]),
+            randomness: RANDOMNESS,
+        })
+    }
+
+    fn fulfill(seed: Bits256) -> Event {
+        Event::Fulfill(Fulfill {
+            seed,
+            randomness: RANDOMNESS,
+        })
+    }
+
+    #[test]
+    fn fulfillment() {
+        let mut requests = Requests::default();
+        assert_eq!(requests.apply(10.into(), Some(1_000), &request(SEED)), None);
+        assert_eq!(requests.apply(11.into(), None, &response(SEED, 1)), None);
+        assert_eq!(requests.apply(12.into(), None, &response(SEED, 2)), None);
+        assert_eq!(requests.pending(), 1);
+
+        assert_eq!(
+            requests.apply(12.into(), Some(3_500), &fulfill(SEED)),
+            Some(Fulfillment {
+                blocks: 2,
+                seconds: Some(2.5),
+                responses: 2,
+            })
+        );
+        assert_eq!(requests.pending(), 0);
+        assert!(requests.requested.is_empty());
+    }
+
+    #[test]
+    fn fulfillment_after_reset() {
+        let mut requests = Requests::default();
+        requests.apply(10.into(), None, &request(SEED));
+        requests.apply(10.into(), None, &response(SEED, 1));
+        requests.apply(11.into(), None, &Event::Reset(Reset { seed: SEED }));
+        requests.apply(12.into(), None, &response(SEED, 2));
+
+        // responses dropped by the reset are not counted
+        assert_eq!(
+            requests.apply(13.into(), Some(1_000), &fulfill(SEED)),
+            Some(Fulfillment {
+                blocks: 3,
+                seconds: None,
+                responses: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn request_before_start() {
+        // latency of a request performed before the stream start is unknown
+        let mut requests = Requests::default();
+        requests.apply(10.into(), None, &response(SEED, 1));
+        assert_eq!(requests.pending(), 1);
+        assert_eq!(requests.apply(11.into(), Some(1_000), &fulfill(SEED)), None);
+        assert_eq!(requests.pending(), 0);
+    }
+
+    #[test]
+    fn position_resume() {
+        let mut position = Position::new(5.into());
+        assert!(position.advance(7.into()));
+        assert!(position.advance(7.into()));
+
+        // the stream fails mid-block and is resumed from the same block
+        assert_eq!(position.resume(), 7.into());
+        assert!(!position.advance(7.into()));
+        assert!(!position.advance(7.into()));
+        assert!(position.advance(7.into()));
+        assert!(position.advance(8.into()));
+
+        // resumed right after moving to the next block
+        assert_eq!(position.resume(), 8.into());
+        assert!(!position.advance(8.into()));
+        assert!(position.advance(9.into()));
+
+        // nothing to skip before the first event
+        let mut position = Position::new(5.into());
+        assert_eq!(position.resume(), 5.into());
+        assert!(position.advance(5.into()));
+    }
+
+    #[test]
+    fn replayed_events_are_skipped() {
+        // events of block 7: request, response | stream fails | response, fulfill
+        let block = [
+            request(SEED),
+            response(SEED, 1),
+            response(SEED, 2),
+            fulfill(SEED),
+        ];
+        let mut requests = Requests::default();
+        let mut position = Position::new(7.into());
+        let mut fulfillments = Vec::new();
+        let mut apply = |events: &[Event], position: &mut Position| {
+            for event in events {
+                if position.advance(7.into()) {
+                    fulfillments.extend(requests.apply(7.into(), Some(0), event));
+                }
+            }
+        };
+        apply(&block[..2], &mut position);
+        position.resume();
+        apply(&block, &mut position);
+
+        assert_eq!(
+            fulfillments,
+            [Fulfillment {
+                blocks: 0,
+                seconds: Some(0.0),
+                responses: 2,
+            }]
+        );
+    }
+}
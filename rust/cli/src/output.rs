@@ -0,0 +1,276 @@
+//! Human-readable and JSON output.
+//!
+//! JSON output of the SDK types is their serde representation
+//! (see `orao_fuel_vrf::serialization`).
+
+use std::fmt::Write;
+
+use fuels::{
+    tx::TxId,
+    types::{Bits256, Bytes32, ContractId, Identity, B512},
+};
+use orao_fuel_vrf::{
+    abi::bindings::FulfillersKeys, randomness_to_bytes64, request::RequestReceipt, Fulfilled,
+    Randomness, RandomnessState, Status,
+};
+use serde_json::{json, Value};
+
+/// Command output.
+pub trait Render {
+    fn text(&self) -> String;
+    fn json(&self) -> Value;
+}
+
+pub fn print(json: bool, output: &impl Render) {
+    if json {
+        println!("{}", output.json());
+    } else {
+        println!("{}", output.text());
+    }
+}
+
+pub fn seed(seed: &Bits256) -> String {
+    Bytes32::new(seed.0).to_string()
+}
+
+pub fn identity(identity: &Identity) -> String {
+    match identity {
+        Identity::Address(x) => format!("address {x}"),
+        Identity::ContractId(x) => format!("contract {x}"),
+    }
+}
+
+impl Render for Status {
+    fn text(&self) -> String {
+        let mut out = String::new();
+        let authority = self
+            .authority
+            .as_ref()
+            .map_or_else(|| "not configured".to_string(), identity);
+        writeln!(out, "Authority: {authority}").unwrap();
+        writeln!(out, "Requests: {}", self.num_requests).unwrap();
+        writeln!(
+            out,
+            "Base asset: fee={}, balance={}",
+            self.base_asset.fee, self.base_asset.balance
+        )
+        .unwrap();
+        if let Some((asset, status)) = &self.additional_asset {
+            writeln!(
+                out,
+                "Additional asset {asset}: fee={}, balance={}",
+                status.fee, status.balance
+            )
+            .unwrap();
+        }
+        write!(out, "Fulfillment authorities: [").unwrap();
+        for address in &self.fulfillment_authorities {
+            write!(out, "\n    {address}").unwrap();
+        }
+        if !self.fulfillment_authorities.is_empty() {
+            writeln!(out).unwrap();
+        }
+        write!(out, "]").unwrap();
+        out
+    }
+
+    fn json(&self) -> Value {
+        json!(self)
+    }
+}
+
+/// Returns state name, randomness and keys of the given request state.
+fn state_parts(state: &RandomnessState) -> (&'static str, B512, &FulfillersKeys) {
+    match state {
+        RandomnessState::Unfulfilled(x) => ("unfulfilled", x.randomness, &x.keys),
+        RandomnessState::Fulfilled(x) => ("fulfilled", x.randomness, &x.keys),
+    }
+}
+
+impl Render for Randomness {
+    fn text(&self) -> String {
+        let (state, randomness, keys) = state_parts(&self.state);
+        let mut out = String::new();
+        writeln!(out, "Seed: {}", seed(&self.seed)).unwrap();
+        writeln!(out, "Sender: {}", identity(&self.sender)).unwrap();
+        writeln!(out, "Callback fee: {}", self.callback_fee).unwrap();
+        writeln!(out, "State: {state}").unwrap();
+        writeln!(out, "Randomness: {}", randomness_to_bytes64(randomness)).unwrap();
+        write!(out, "Keys: [").unwrap();
+        for key in keys.iter() {
+            write!(out, "\n    {key}").unwrap();
+        }
+        if !keys.is_empty() {
+            writeln!(out).unwrap();
+        }
+        write!(out, "]").unwrap();
+        out
+    }
+
+    fn json(&self) -> Value {
+        json!(self)
+    }
+}
+
+impl Render for Vec<Randomness> {
+    fn text(&self) -> String {
+        self.iter()
+            .map(Render::text)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn json(&self) -> Value {
+        self.iter().map(Render::json).collect()
+    }
+}
+
+/// Fulfilled randomness of the given seed.
+pub struct FulfilledOutput<'a> {
+    pub seed: Bits256,
+    pub fulfilled: &'a Fulfilled,
+}
+
+impl Render for FulfilledOutput<'_> {
+    fn text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Seed: {}", seed(&self.seed)).unwrap();
+        writeln!(
+            out,
+            "Randomness: {}",
+            randomness_to_bytes64(self.fulfilled.randomness)
+        )
+        .unwrap();
+        write!(out, "Fulfilled by: [").unwrap();
+        for key in self.fulfilled.keys.iter() {
+            write!(out, "\n    {key}").unwrap();
+        }
+        write!(out, "\n]").unwrap();
+        out
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "seed": Bytes32::new(self.seed.0),
+            "state": RandomnessState::Fulfilled(self.fulfilled.clone()),
+        })
+    }
+}
+
+impl Render for RequestReceipt {
+    fn text(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Request: no={}, seed={}", self.num, seed(&self.seed)).unwrap();
+        write!(
+            out,
+            "Paid: fee={}, callback_fee={}, asset={}",
+            self.fee, self.callback_fee, self.asset
+        )
+        .unwrap();
+        if let Some(tx_id) = self.tx_id {
+            write!(out, "\nTransaction: {tx_id}").unwrap();
+        }
+        out
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "num": self.num,
+            "seed": Bytes32::new(self.seed.0),
+            "asset_id": self.asset,
+            "fee": self.fee,
+            "callback_fee": self.callback_fee,
+            "tx_id": self.tx_id,
+        })
+    }
+}
+
+/// Submitted transaction.
+pub struct TxOutput(pub Option<TxId>);
+
+impl Render for TxOutput {
+    fn text(&self) -> String {
+        match self.0 {
+            Some(tx_id) => format!("Transaction: {tx_id}"),
+            None => "Transaction submitted".to_string(),
+        }
+    }
+
+    fn json(&self) -> Value {
+        json!({ "tx_id": self.0 })
+    }
+}
+
+/// Proxy state.
+pub struct ProxyOutput {
+    pub contract_id: ContractId,
+    pub target: Option<ContractId>,
+    pub owner: Option<Identity>,
+}
+
+impl Render for ProxyOutput {
+    fn text(&self) -> String {
+        format!(
+            "Proxy: {}\nTarget: {}\nOwner: {}",
+            self.contract_id,
+            self.target
+                .map_or_else(|| "not set".to_string(), |x| x.to_string()),
+            self.owner
+                .as_ref()
+                .map_or_else(|| "not set".to_string(), identity),
+        )
+    }
+
+    fn json(&self) -> Value {
+        json!({
+            "contract_id": self.contract_id,
+            "target": self.target,
+            "owner": self.owner,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::types::{Address, AssetId};
+    use orao_fuel_vrf::{AssetStatus, Unfulfilled};
+
+    use super::*;
+
+    #[test]
+    fn randomness_json() {
+        let randomness = Randomness {
+            sender: Identity::Address(Address::new([1; 32])),
+            seed: Bits256([2; 32]),
+            callback_fee: 3,
+            state: RandomnessState::Unfulfilled(Unfulfilled {
+                randomness: B512::from((Bits256([4; 32]), Bits256([5; 32]))),
+                keys: FulfillersKeys {
+                    keys: Default::default(),
+                },
+            }),
+        };
+        let value = randomness.json();
+        assert_eq!(value, serde_json::to_value(&randomness).unwrap());
+        assert_eq!(value["state"]["type"], "unfulfilled");
+        assert_eq!(
+            serde_json::from_value::<Randomness>(value).unwrap(),
+            randomness
+        );
+    }
+
+    #[test]
+    fn status_json() {
+        let status = Status {
+            authority: Some(Identity::Address(Address::new([1; 32]))),
+            num_requests: 2,
+            base_asset: AssetStatus { fee: 3, balance: 4 },
+            fulfillment_authorities: vec![Address::new([5; 32])],
+            additional_asset: Some((AssetId::new([6; 32]), AssetStatus { fee: 7, balance: 8 })),
+        };
+        assert_eq!(
+            serde_json::from_value::<Status>(status.json()).unwrap(),
+            status
+        );
+    }
+}
@@ -0,0 +1,33 @@
+use anyhow::Context;
+use fuels::prelude::*;
+use orao_fuel_vrf::wallet::Mnemonic;
+
+use crate::Args;
+
+/// Loads the account from the private key, if given, or from the forc-wallet keystore.
+pub fn load(args: &Args, provider: Provider) -> anyhow::Result<WalletUnlocked> {
+    let wallet = match &args.private_key {
+        Some(private_key) => orao_fuel_vrf::wallet::from_private_key(private_key, provider)
+            .context("Invalid private key")?,
+        None => {
+            let wallet_password = match &args.wallet_password {
+                Some(wallet_password) => wallet_password.clone(),
+                None => rpassword::prompt_password("Wallet password: ")?,
+            };
+
+            let wallet_path = std::fs::canonicalize(shellexpand::tilde(&args.wallet).as_ref())
+                .with_context(|| format!("Invalid wallet path {}", args.wallet))?;
+
+            Mnemonic::from_keystore(&wallet_path, &wallet_password)
+                .context("Unable to decrypt the wallet")?
+                .wallet(args.account_index, provider)?
+        }
+    };
+    eprintln!("Using wallet address: {}", wallet.address());
+    Ok(wallet)
+}
+
+/// Read-only account for commands that don't send transactions.
+pub fn read_only(provider: Provider) -> ImpersonatedAccount {
+    ImpersonatedAccount::new(Bech32Address::default(), Some(provider))
+}
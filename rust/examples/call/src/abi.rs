@@ -1,5 +1,8 @@
 use fuels::{prelude::*, types::Bits256};
-use orao_fuel_vrf::{Vrf, TESTNET_CONTRACT_ID};
+use orao_fuel_vrf::{Network, Vrf};
+
+/// Amount paid on top of the VRF fee to cover the game callback.
+const CALLBACK_FEE: u64 = 10_000;
 
 pub mod bindings {
     include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
@@ -35,7 +38,7 @@ impl<T: Account> bindings::RussianRoulette<T> {
             Bech32Address::default(),
             Some(self.account().try_provider().unwrap().clone()),
         );
-        let vrf = Vrf::new(TESTNET_CONTRACT_ID, account).await;
+        let vrf = Vrf::connect(Network::Testnet, account).await?;
         let contract_ids = vrf.contract_ids();
 
         Ok(self
@@ -53,7 +56,7 @@ impl<T: Account> bindings::RussianRoulette<T> {
             Bech32Address::default(),
             Some(self.account().try_provider().unwrap().clone()),
         );
-        let vrf = Vrf::new(TESTNET_CONTRACT_ID, account).await;
+        let vrf = Vrf::connect(Network::Testnet, account).await?;
         let mut contract_ids = vrf.contract_ids();
         contract_ids.push(crate::CONTRACT_ID.into());
 
@@ -75,29 +78,27 @@ impl<T: Account> bindings::RussianRoulette<T> {
             Bech32Address::default(),
             Some(self.account().try_provider().unwrap().clone()),
         );
-        let vrf = Vrf::new(TESTNET_CONTRACT_ID, account).await;
+        let vrf = Vrf::connect(Network::Testnet, account).await?;
         let contract_ids = vrf.contract_ids();
 
         // using random "force" - generates a boolean
-        let force = rand::random();
+        let force = Bits256(rand::random());
 
-        // we need to get the correct fee
-        let fee = self
-            .methods()
-            .round_cost()
-            .with_contract_ids(&contract_ids)
-            .simulate(Execution::StateReadOnly)
-            .await?
-            .value;
+        // we need to get the correct fee and pay for the callback on top of it
+        let (_, amount) = vrf
+            .request_builder(force)
+            .callback_fee(CALLBACK_FEE)
+            .amount()
+            .await?;
 
-        println!("VRF fee is: {:?}", fee);
+        println!("VRF fee is: {:?}", amount - CALLBACK_FEE);
 
         self.methods()
-            .spin_and_pull_the_trigger(Bits256(force), bet_amount)
+            .spin_and_pull_the_trigger(force, bet_amount)
             // this is necessary, because our contract calls VRF contract
             .with_contract_ids(&contract_ids)
             .with_tx_policies(TxPolicies::default())
-            .call_params(CallParameters::default().with_amount(bet_amount + fee + 10000))? // fee + CALLBACK_FEE
+            .call_params(CallParameters::default().with_amount(bet_amount + amount))?
             .call()
             .await?;
 
@@ -10,5 +10,4 @@ pub enum Error {
     UnknownSeed: (),
     PlayerNotFound: (),
     SeedMismatch: (),
-    OnlyVrfCanFulfill: (),
 }
\ No newline at end of file
@@ -3,6 +3,7 @@ use std::time::{Duration, Instant};
 use clap::Parser;
 use fuels::prelude::*;
 use indicatif::ProgressBar;
+use orao_fuel_vrf::wallet::Mnemonic;
 use tokio::time::{sleep, timeout};
 
 use crate::abi::bindings::{RandomnessState, Status};
@@ -13,7 +14,6 @@ pub const CONTRACT_ID: ContractId = ContractId::new([
 ]);
 
 mod abi;
-mod utils;
 
 /// Fuel VRF on-chain call example.
 #[derive(Debug, Parser)]
@@ -55,9 +55,7 @@ async fn main() -> anyhow::Result<()> {
     let wallet_path = std::fs::canonicalize(shellexpand::tilde(&args.wallet).as_ref())
         .expect("Valid wallet path");
 
-    let secret_key =
-        utils::derive_account_with_index(&wallet_password, &wallet_path, args.account_index)
-            .expect("Valid account");
+    let mnemonic = Mnemonic::from_keystore(&wallet_path, &wallet_password).expect("Valid wallet");
 
     eprintln!("Using node address: {}", args.endpoint);
     let provider = Provider::connect(args.endpoint);
@@ -68,7 +66,7 @@ async fn main() -> anyhow::Result<()> {
 
     eprintln!("Contract address: {}\n", args.contract_id);
 
-    let wallet = WalletUnlocked::new_from_private_key(secret_key, Some(provider));
+    let wallet = mnemonic.wallet(args.account_index, provider)?;
     let address = Address::from(wallet.address());
     eprintln!("Player address: {}", address.clone());
 
@@ -26,9 +26,13 @@ use std::hash::Hash;
 use error::Error;
 
 pub use vrf_abi::{randomness::{Fulfilled, Randomness, RandomnessState, Unfulfilled}, Vrf, Consumer};
+use vrf_abi::{consumer::{only_vrf, request_fee, request_randomness}, derivation::chance};
+
+configurable {
+    /// Overridden in tests to point to the mock VRF.
+    VRF_ID: b256 = 0x2a8d96911becbe05b2a9f5253c91865f0f4b365ed0e2abab17a35e9fc9c4ac76,
+}
 
-const VRF_ID: b256 = 0x2a8d96911becbe05b2a9f5253c91865f0f4b365ed0e2abab17a35e9fc9c4ac76;
-const THRESHOLD: b256 = 0x0aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa; // 1/6 of max b256 value
 const MAX_BET: u64 = 1_000_000;
 
 abi RussianRoulette {
@@ -51,9 +55,8 @@ enum RoundOutcome {
 
 impl RoundOutcome {
     fn derive(random: B512) -> Self {
-        // roughly 1/6 chance
-        if random.bits()[0] <= THRESHOLD
-        {
+        // one chamber out of six
+        if chance(random, 0, 1, 6) {
             RoundOutcome::Bang
         } else {
             RoundOutcome::Click
@@ -79,19 +82,11 @@ storage {
     force_to_player: StorageMap<b256, Identity> = StorageMap {},
 }
 
-fn only_vrf() {
-    let vrf_id = Identity::ContractId(ContractId::from(VRF_ID));
-    if msg_sender().unwrap() != vrf_id {
-        log(Error::OnlyVrfCanFulfill);
-        revert(3);
-    }
-}
-
 impl Consumer for Contract {
     #[storage(read, write)]
     fn fulfill_randomness(seed: b256, randomness: B512) {
         // Restrict access to only the VRF contract
-        only_vrf();
+        only_vrf(VRF_ID);
 
         // Retrieve the player associated with the seed
         let player_id = match storage.force_to_player.get(seed).try_read() {
@@ -217,17 +212,19 @@ impl RussianRoulette for Contract {
         storage.player_state.insert(sender, player);
         storage.force_to_player.insert(force, sender);
 
-        let vrf = abi(Vrf, VRF_ID);
-
-        let fee = vrf.get_fee(AssetId::base());
+        let fee = request_fee(VRF_ID, AssetId::base());
         if fee + bet_amount > amount {
             log(Error::InvalidAmount);
             revert(2);
         }
 
-        let _ = vrf.request {
-            asset_id: AssetId::base().bits(),
-            coins: amount - bet_amount,
-        }(force);
+        // the rest of the payment covers the callback
+        let _ = request_randomness(
+            VRF_ID,
+            AssetId::base(),
+            force,
+            fee,
+            amount - bet_amount - fee,
+        );
     }
 }
@@ -4,11 +4,11 @@ use clap::Parser;
 use fuels::prelude::*;
 use fuels::types::{Bits256, Bytes32};
 use indicatif::ProgressBar;
-use orao_fuel_vrf::{randomness_to_bytes64, Event, Fulfilled, RandomnessState, Vrf};
+use orao_fuel_vrf::{
+    randomness_to_bytes64, wait::WaitOptions, wallet::Mnemonic, Error, Event, Fulfilled, Vrf,
+};
 use tokio::time::timeout;
 
-mod utils;
-
 /// Fuel VRF off-chain example.
 #[derive(Debug, Parser)]
 pub struct Args {
@@ -34,7 +34,7 @@ pub struct Args {
 
     /// Request seed.
     ///
-    /// Will generate random seed, if not given.
+    /// Will generate random unused seed, if not given.
     #[arg(long)]
     pub seed: Option<Address>,
 }
@@ -51,9 +51,7 @@ async fn main() -> anyhow::Result<()> {
     let wallet_path = std::fs::canonicalize(shellexpand::tilde(&args.wallet).as_ref())
         .expect("Valid wallet path");
 
-    let secret_key =
-        utils::derive_account_with_index(&wallet_password, &wallet_path, args.account_index)
-            .expect("Valid account");
+    let mnemonic = Mnemonic::from_keystore(&wallet_path, &wallet_password).expect("Valid wallet");
 
     eprintln!("Using node address: {}", args.endpoint);
     let provider = Provider::connect(args.endpoint);
@@ -62,66 +60,50 @@ async fn main() -> anyhow::Result<()> {
     };
     let provider = provider?;
 
-    let wallet = WalletUnlocked::new_from_private_key(secret_key, Some(provider.clone()));
+    let wallet = mnemonic.wallet(args.account_index, provider)?;
     eprintln!("Using wallet address: {}", wallet.address());
 
     eprintln!("Using contract address: {}\n", args.contract_id);
 
-    let instance = Vrf::new(args.contract_id, wallet).await;
-
-    let consensus_parameters = provider.consensus_parameters().await?;
-    let base_asset = consensus_parameters.base_asset_id();
+    let instance = Vrf::try_new(args.contract_id, wallet).await?;
 
-    let seed = args.seed.unwrap_or_else(|| rand::random());
+    let seed = match args.seed {
+        Some(seed) => Bits256(*seed),
+        None => instance.fresh_seed().await?,
+    };
 
-    println!("Using seed: {}", Bytes32::new(*seed));
+    println!("Using seed: {}", Bytes32::new(seed.0));
 
-    let fee = instance.get_fee(*base_asset).await?;
     let progress = ProgressBar::new_spinner();
     progress.enable_steady_tick(Duration::from_millis(120));
     progress.set_message("Requesting randomness..");
-    let response = instance
-        .request(Bits256(*seed))
-        .with_tx_policies(TxPolicies::default())
-        .call_params(
-            CallParameters::default()
-                .with_amount(fee)
-                .with_asset_id(*base_asset),
-        )?
-        .call()
-        .await?;
+    let receipt = instance.request_builder(seed).send().await?;
     progress.suspend(|| {
-        let events = response
-            .decode_logs_with_type::<Event>()
-            .expect("being able to parse logs");
-        for event in events {
-            println!("{event}");
+        if let Some(event) = receipt.event {
+            println!("{}", Event::Request(event));
         }
     });
 
     progress.set_message("Waiting for randomness to be fulfilled..");
-    loop {
-        let Some(x) = instance.get_request_by_num(response.value).await? else {
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-            continue;
-        };
-
-        let RandomnessState::Fulfilled(Fulfilled { randomness, keys }) = x.state else {
-            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-            continue;
-        };
-
-        progress.suspend(|| {
-            println!("Seed: {}", Bytes32::new(x.seed.0));
-            println!("Randomness: {}", randomness_to_bytes64(randomness));
-            println!("Fulfilled by: [");
-            for key in keys.iter() {
-                println!("    {}", Address::from(*key).to_string());
+    let Fulfilled { randomness, keys } = loop {
+        let options = WaitOptions::default().with_fail_on_reset();
+        match instance.wait_fulfilled(receipt.num, options).await {
+            Err(Error::Reset { .. }) => {
+                progress.suspend(|| println!("Request is reset, waiting for new responses.."))
             }
-            println!("]");
-        });
-        break;
-    }
+            result => break result?,
+        }
+    };
+
+    progress.suspend(|| {
+        println!("Seed: {}", Bytes32::new(seed.0));
+        println!("Randomness: {}", randomness_to_bytes64(randomness));
+        println!("Fulfilled by: [");
+        for key in keys.iter() {
+            println!("    {key}");
+        }
+        println!("]");
+    });
 
     progress.finish_with_message("Done");
 
@@ -8,13 +8,15 @@ fn main() {
 
     let contract_abi_path = format!("{manifest_dir}/abi/vrf.json");
     let proxy_abi_path = format!("{manifest_dir}/abi/proxy.json");
+    let mock_abi_path = format!("{manifest_dir}/abi/mock.json");
 
     // Tell Cargo that if ABI changes, to rerun this build script.
     println!("cargo:rerun-if-changed={manifest_dir}/abi/vrf.json");
     println!("cargo:rerun-if-changed={manifest_dir}/abi/proxy.json");
+    println!("cargo:rerun-if-changed={manifest_dir}/abi/mock.json");
     write(
         &dest_path,
-        format!(r#"fuels::prelude::abigen!(Contract(name="Vrf", abi="{contract_abi_path}"), Contract(name="Proxy", abi="{proxy_abi_path}"));"#),
+        format!(r#"fuels::prelude::abigen!(Contract(name="Vrf", abi="{contract_abi_path}"), Contract(name="Proxy", abi="{proxy_abi_path}"), Contract(name="VrfMock", abi="{mock_abi_path}"));"#),
     )
     .unwrap();
 }
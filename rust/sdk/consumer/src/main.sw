@@ -0,0 +1,66 @@
+contract;
+
+use std::{
+    asset::transfer,
+    b512::B512,
+    call_frames::msg_asset_id,
+    context::msg_amount,
+    logging::log,
+};
+
+use vrf_abi::{consumer::only_vrf, Consumer, Vrf};
+
+/// Sample consumer deployed by the SDK test environment (`orao_fuel_vrf::testing`).
+///
+/// The VRF is passed to every call, so that the same consumer works with
+/// the VRF and with the mock.
+abi SampleConsumer {
+    /// Requests randomness from the VRF forwarding the coins it is called with.
+    #[payable]
+    #[storage(write)]
+    fn request(vrf: ContractId, seed: b256);
+
+    /// Makes the VRF execute the callback of the fulfilled request.
+    #[storage(write)]
+    fn execute_callback(vrf: ContractId, seed: b256);
+}
+
+/// Logged by the callback.
+pub struct RandomnessFulfilled {
+    pub seed: b256,
+    pub randomness: B512,
+}
+
+storage {
+    /// VRF of the last call, callbacks are only accepted from it.
+    vrf: ContractId = ContractId::zero(),
+}
+
+impl SampleConsumer for Contract {
+    #[payable]
+    #[storage(write)]
+    fn request(vrf: ContractId, seed: b256) {
+        storage.vrf.write(vrf);
+        let _ = abi(Vrf, vrf.bits()).request {
+            asset_id: msg_asset_id().bits(),
+            coins: msg_amount(),
+        }(seed);
+    }
+
+    #[storage(write)]
+    fn execute_callback(vrf: ContractId, seed: b256) {
+        storage.vrf.write(vrf);
+        abi(Vrf, vrf.bits()).execute_callback(seed);
+    }
+}
+
+impl Consumer for Contract {
+    /// Logs the randomness and transfers a coin of the base asset
+    /// to the address equal to the seed.
+    #[storage(read, write)]
+    fn fulfill_randomness(seed: b256, randomness: B512) {
+        only_vrf(storage.vrf.read().bits());
+        log(RandomnessFulfilled { seed, randomness });
+        transfer(Identity::Address(Address::from(seed)), AssetId::base(), 1);
+    }
+}
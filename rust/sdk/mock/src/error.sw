@@ -0,0 +1,22 @@
+library;
+
+/// Mirrors the VRF contract errors so that they are decoded the same way.
+pub enum Error {
+    ContractNotConfigured: (),
+    AssetNotConfigured: (),
+    NotAuthorized: (),
+    RemainingAssets: (),
+    NonZeroFee: (),
+    ZeroAuthority: (),
+    ZeroFee: (),
+    NoFeePaid: (),
+    WrongFeePaid: (),
+    SeedInUse: (),
+    NoAmountSpecified: (),
+    NotEnoughFunds: (),
+    UnknownRequest: (),
+    InvalidResponse: (),
+    Responded: (),
+    Fulfilled: (),
+    UnFulfilled: (),
+}
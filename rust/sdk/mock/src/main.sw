@@ -0,0 +1,150 @@
+contract;
+
+mod error;
+
+use std::{
+    auth::msg_sender,
+    b512::B512,
+    call_frames::msg_asset_id,
+    context::msg_amount,
+    hash::Hash,
+    identity::Identity,
+    revert::require,
+    storage::*,
+};
+
+use error::Error;
+
+use vrf_abi::{
+    randomness::{Fulfilled, FulfillersKeys, Randomness, RandomnessState},
+    Consumer,
+    Vrf,
+};
+
+/// Test controls of the mock.
+abi VrfMock {
+    /// Sets the base asset fee (zero by default).
+    #[storage(write)]
+    fn mock_set_fee(fee: u64);
+
+    /// Fulfills the request with the given randomness and invokes
+    /// `Consumer::fulfill_randomness` if the request is made by a contract.
+    #[storage(read, write)]
+    fn mock_fulfill(seed: b256, randomness: B512);
+}
+
+storage {
+    fee: u64 = 0,
+    num_requests: u64 = 0,
+    requests: StorageMap<b256, Randomness> = StorageMap {},
+    seeds: StorageMap<u64, b256> = StorageMap {},
+}
+
+fn is_fulfilled(request: Randomness) -> bool {
+    match request.state {
+        RandomnessState::Unfulfilled(_) => false,
+        RandomnessState::Fulfilled(_) => true,
+    }
+}
+
+fn fulfill_randomness(request: Randomness) {
+    if let Identity::ContractId(client) = request.sender {
+        if let RandomnessState::Fulfilled(fulfilled) = request.state {
+            abi(Consumer, client.bits()).fulfill_randomness(request.seed, fulfilled.randomness);
+        }
+    }
+}
+
+impl VrfMock for Contract {
+    #[storage(write)]
+    fn mock_set_fee(fee: u64) {
+        storage.fee.write(fee);
+    }
+
+    #[storage(read, write)]
+    fn mock_fulfill(seed: b256, randomness: B512) {
+        let request = storage.requests.get(seed).try_read();
+        require(request.is_some(), Error::UnknownRequest);
+        let mut request = request.unwrap();
+        require(!is_fulfilled(request), Error::Fulfilled);
+
+        request.state = RandomnessState::Fulfilled(Fulfilled {
+            randomness,
+            keys: FulfillersKeys::new(),
+        });
+        storage.requests.insert(seed, request);
+
+        fulfill_randomness(request);
+    }
+}
+
+impl Vrf for Contract {
+    #[storage(read)]
+    fn get_asset() -> AssetId {
+        AssetId::base()
+    }
+
+    #[storage(read)]
+    fn get_fee(asset: AssetId) -> u64 {
+        if asset == AssetId::base() {
+            storage.fee.read()
+        } else {
+            0
+        }
+    }
+
+    #[storage(read)]
+    fn get_fulfillment_authorities() -> Vec<Address> {
+        Vec::new()
+    }
+
+    #[storage(read)]
+    fn get_request_by_seed(seed: b256) -> Option<Randomness> {
+        storage.requests.get(seed).try_read()
+    }
+
+    #[storage(read)]
+    fn get_request_by_num(num: u64) -> Option<Randomness> {
+        match storage.seeds.get(num).try_read() {
+            Some(seed) => storage.requests.get(seed).try_read(),
+            None => None,
+        }
+    }
+
+    #[payable]
+    #[storage(read, write)]
+    fn request(seed: b256) -> u64 {
+        require(msg_asset_id() == AssetId::base(), Error::AssetNotConfigured);
+        require(
+            storage
+                .requests
+                .get(seed)
+                .try_read()
+                .is_none(),
+            Error::SeedInUse,
+        );
+        let fee = storage.fee.read();
+        let amount = msg_amount();
+        require(amount >= fee, Error::WrongFeePaid);
+
+        let num = storage.num_requests.read();
+        storage
+            .requests
+            .insert(
+                seed,
+                Randomness::new(msg_sender().unwrap(), seed, amount - fee),
+            );
+        storage.seeds.insert(num, seed);
+        storage.num_requests.write(num + 1);
+        num
+    }
+
+    #[storage(read, write)]
+    fn execute_callback(seed: b256) {
+        let request = storage.requests.get(seed).try_read();
+        require(request.is_some(), Error::UnknownRequest);
+        let request = request.unwrap();
+        require(is_fulfilled(request), Error::UnFulfilled);
+        fulfill_randomness(request);
+    }
+}
@@ -2,7 +2,7 @@ use std::fmt;
 
 use fuels::{
     prelude::*,
-    types::{Bits256, Identity, B512, Bytes32, Bytes64},
+    types::{Bits256, Bytes32, Bytes64, Identity, B512},
 };
 
 pub mod bindings {
@@ -37,6 +37,26 @@ impl fmt::Display for bindings::Error {
     }
 }
 
+impl std::error::Error for bindings::AccessError {}
+impl fmt::Display for bindings::AccessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            bindings::AccessError::NotOwner => f.write_str("caller is not the owner"),
+        }
+    }
+}
+
+impl std::error::Error for bindings::InitializationError {}
+impl fmt::Display for bindings::InitializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            bindings::InitializationError::CannotReinitialized => {
+                f.write_str("ownership is already initialized")
+            }
+        }
+    }
+}
+
 impl<T: Account> fmt::Debug for bindings::VrfMethods<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("VrfMethods").finish_non_exhaustive()
@@ -86,8 +106,8 @@ impl fmt::Display for bindings::Request {
             self.no,
             Bytes32::new(self.seed.0),
             match self.client {
-                Identity::Address(x) => Address::from(x).to_string(),
-                Identity::ContractId(x) => ContractId::from(x).to_string(),
+                Identity::Address(x) => x.to_string(),
+                Identity::ContractId(x) => x.to_string(),
             },
         )
     }
@@ -99,7 +119,7 @@ impl fmt::Display for bindings::Response {
             f,
             "Response: seed={}, authority={}, randomness={}",
             Bytes32::from(self.seed.0),
-            Address::from(self.authority),
+            self.authority,
             randomness_to_bytes64(self.randomness),
         )
     }
@@ -118,7 +138,7 @@ impl fmt::Display for bindings::Callback {
             "Callback: seed={}, randomness={}, contract_id={}",
             Bytes32::new(self.seed.0),
             randomness_to_bytes64(self.randomness),
-            ContractId::from(self.client),
+            self.client,
         )
     }
 }
@@ -171,3 +191,12 @@ pub fn randomness_to_bytes64(randomness: B512) -> Bytes64 {
     bytes[32..].copy_from_slice(&randomness.bytes[1].0);
     Bytes64::new(bytes)
 }
+
+/// Inverse of [`randomness_to_bytes64`].
+pub fn bytes64_to_randomness(bytes: Bytes64) -> B512 {
+    let mut fst = [0_u8; 32];
+    fst.copy_from_slice(&bytes[..32]);
+    let mut snd = [0_u8; 32];
+    snd.copy_from_slice(&bytes[32..]);
+    B512::from((Bits256(fst), Bits256(snd)))
+}
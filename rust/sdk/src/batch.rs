@@ -0,0 +1,126 @@
+//! Batched request lookups.
+
+use std::fmt::Debug;
+
+use fuels::{
+    core::traits::{Parameterize, Tokenizable},
+    prelude::*,
+    programs::calls::{CallHandler, ContractCall},
+    types::{errors::error, Bits256, Token},
+};
+
+use crate::{Randomness, Result, Vrf, MULTI_CALL_GAS_LIMIT};
+
+/// Gas budget of a single lookup in a multi-call.
+///
+/// A lookup of a fulfilled request (the largest one) costs about 8k gas on
+/// a local node, the rest leaves room for the proxy and for gas costs
+/// of other networks.
+pub const LOOKUP_GAS: u64 = 20_000;
+
+/// Number of lookups packed into a single multi-call, so that a batch
+/// fits [`MULTI_CALL_GAS_LIMIT`] given [`LOOKUP_GAS`] per lookup.
+pub const BATCH_SIZE: usize = (MULTI_CALL_GAS_LIMIT / LOOKUP_GAS) as usize;
+
+/// Outputs of a multi-call with calls of the same type.
+#[derive(Debug)]
+struct Batch<T>(Vec<T>);
+
+impl<T: Tokenizable> Tokenizable for Batch<T> {
+    fn from_token(token: Token) -> fuels::types::errors::Result<Self> {
+        match token {
+            Token::Tuple(tokens) => tokens
+                .into_iter()
+                .map(T::from_token)
+                .collect::<fuels::types::errors::Result<_>>()
+                .map(Batch),
+            other => Err(error!(
+                Codec,
+                "expected a tuple of call outputs, got {other:?}"
+            )),
+        }
+    }
+
+    fn into_token(self) -> Token {
+        Token::Tuple(self.0.into_iter().map(Tokenizable::into_token).collect())
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Returns requests with the given seeds in the input order.
+    ///
+    /// Lookups are performed in multi-calls of [`BATCH_SIZE`].
+    pub async fn get_requests_by_seeds(
+        &self,
+        seeds: &[Bits256],
+    ) -> Result<Vec<Option<Randomness>>> {
+        self.batch(seeds, |seed| self.methods.get_request_by_seed(*seed))
+            .await
+    }
+
+    /// Returns requests with the given numbers in the input order.
+    ///
+    /// Lookups are performed in multi-calls of [`BATCH_SIZE`].
+    pub async fn get_requests_by_nums(&self, nums: &[u64]) -> Result<Vec<Option<Randomness>>> {
+        self.batch(nums, |num| self.methods.get_request_by_num(*num))
+            .await
+    }
+
+    async fn batch<I, T: Tokenizable + Parameterize + Debug>(
+        &self,
+        inputs: &[I],
+        call: impl Fn(&I) -> CallHandler<A, ContractCall, T>,
+    ) -> Result<Vec<T>> {
+        let mut outputs = Vec::with_capacity(inputs.len());
+        for chunk in inputs.chunks(BATCH_SIZE) {
+            let mut multi_call = CallHandler::new_multi_call(self.abi.account()).with_tx_policies(
+                TxPolicies::default().with_script_gas_limit(MULTI_CALL_GAS_LIMIT),
+            );
+            for input in chunk {
+                multi_call = multi_call.add_call(self.with_target_contract(call(input)));
+            }
+            let response = multi_call
+                .simulate::<Batch<T>>(Execution::StateReadOnly)
+                .await
+                .map_err(|e| self.decode_error(e))?;
+            outputs.extend(response.value.0);
+        }
+        Ok(outputs)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::{TestConfig, TestEnv};
+
+    #[tokio::test]
+    async fn lookups() -> Result<()> {
+        let env = TestEnv::launch_with(TestConfig::default()).await?;
+        let seed = Bits256([1; 32]);
+        env.request(seed).await?;
+        env.fulfill(seed).await?;
+
+        let response = CallHandler::new_multi_call(env.vrf.abi.account())
+            .with_tx_policies(TxPolicies::default().with_script_gas_limit(MULTI_CALL_GAS_LIMIT))
+            .add_call(env.vrf.methods.get_request_by_seed(seed))
+            .simulate::<Batch<Option<Randomness>>>(Execution::StateReadOnly)
+            .await?;
+        assert!(response.gas_used <= LOOKUP_GAS);
+
+        // a full batch of unknown seeds followed by the known one
+        let mut seeds = (0..BATCH_SIZE as u64)
+            .map(|i| {
+                let mut seed = [0; 32];
+                seed[..8].copy_from_slice(&i.to_be_bytes());
+                Bits256(seed)
+            })
+            .collect::<Vec<_>>();
+        seeds.push(seed);
+        let requests = env.vrf.get_requests_by_seeds(&seeds).await?;
+        assert_eq!(requests.len(), BATCH_SIZE + 1);
+        assert!(requests[..BATCH_SIZE].iter().all(Option::is_none));
+        assert_eq!(requests[BATCH_SIZE].as_ref().map(|x| x.seed), Some(seed));
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+//! Derivation of application values from fulfilled randomness.
+//!
+//! Comparing a part of the randomness against a threshold or taking it modulo
+//! the number of outcomes is biased. Functions of this module produce unbiased
+//! outputs instead.
+//!
+//! Every output is a function of the fulfilled randomness and a label only,
+//! so anyone is able to reproduce it given the request seed (the randomness
+//! is available via [`Vrf::get_request_by_seed`]). Derivation scheme:
+//!
+//! -   `key = sha256(randomness || label)` (see [`sub_value`]),
+//! -   the byte stream is `sha256(key || 0) || sha256(key || 1) || …`
+//!     with big-endian `u64` counters,
+//! -   integers are read from the stream as big-endian `u64`,
+//! -   [`DerivedRng::uniform`] rejects values below `2^64 mod n`
+//!     and takes the rest modulo `n`.
+//!
+//! Use distinct labels for independent values of the same request. Helpers of
+//! the `vrf_abi::derivation` Sway module follow the same scheme with
+//! the big-endian `u64` index as the label, so on-chain outcomes could be
+//! reproduced off-chain.
+//!
+//! ```
+//! # use fuels::types::{Bits256, B512};
+//! # use orao_fuel_vrf::derive;
+//! # let randomness = B512::from((Bits256([1; 32]), Bits256([2; 32])));
+//! let number = derive::uniform(randomness, b"number", 1..7);
+//! let mut deck = (0..52).collect::<Vec<u8>>();
+//! derive::shuffle(randomness, b"deck", &mut deck);
+//! let rarity = derive::weighted_choice(randomness, b"rarity", &[70, 25, 5]);
+//! # assert!((1..7).contains(&number));
+//! # assert!(rarity.is_some());
+//! ```
+//!
+//! [`Vrf::get_request_by_seed`]: crate::Vrf::get_request_by_seed
+
+use std::ops::Range;
+
+use fuels::{
+    crypto::Hasher,
+    types::{Bits256, B512},
+};
+use rand::RngCore;
+
+use crate::randomness_to_bytes64;
+
+/// Returns a sub-value of the randomness keyed by the label.
+///
+/// Sub-values of distinct labels are independent.
+pub fn sub_value(randomness: B512, label: &[u8]) -> Bits256 {
+    Bits256(
+        *Hasher::default()
+            .chain(randomness_to_bytes64(randomness))
+            .chain(label)
+            .finalize(),
+    )
+}
+
+/// Returns `count` independent sub-values keyed by the label
+/// (the first blocks of the label's byte stream).
+pub fn sub_values(randomness: B512, label: &[u8], count: usize) -> Vec<Bits256> {
+    let mut rng = DerivedRng::new(randomness, label);
+    (0..count).map(|_| Bits256(rng.next_block())).collect()
+}
+
+/// Returns a uniformly distributed integer in the range.
+///
+/// # Panics
+///
+/// Panics if the range is empty.
+pub fn uniform(randomness: B512, label: &[u8], range: Range<u64>) -> u64 {
+    DerivedRng::new(randomness, label).uniform(range)
+}
+
+/// Shuffles the slice uniformly.
+pub fn shuffle<T>(randomness: B512, label: &[u8], items: &mut [T]) {
+    DerivedRng::new(randomness, label).shuffle(items)
+}
+
+/// Returns an index chosen with the probability proportional to its weight
+/// (`None` if all weights are zero).
+///
+/// # Panics
+///
+/// Panics if the sum of weights overflows `u64`.
+pub fn weighted_choice(randomness: B512, label: &[u8], weights: &[u64]) -> Option<usize> {
+    DerivedRng::new(randomness, label).weighted_choice(weights)
+}
+
+/// Deterministic random number generator over the label's byte stream.
+///
+/// Implements [`RngCore`], so it could be used with the `rand` ecosystem,
+/// though the outputs of `rand` distributions are only reproducible
+/// with the same `rand` version.
+#[derive(Debug, Clone)]
+pub struct DerivedRng {
+    key: [u8; 32],
+    counter: u64,
+    block: [u8; 32],
+    /// Number of consumed bytes of the current block.
+    position: usize,
+}
+
+impl DerivedRng {
+    /// Creates a generator over the byte stream of the label.
+    pub fn new(randomness: B512, label: &[u8]) -> Self {
+        Self {
+            key: sub_value(randomness, label).0,
+            counter: 0,
+            block: [0; 32],
+            position: 32,
+        }
+    }
+
+    fn next_block(&mut self) -> [u8; 32] {
+        let block = *Hasher::default()
+            .chain(self.key)
+            .chain(self.counter.to_be_bytes())
+            .finalize();
+        self.counter += 1;
+        block
+    }
+
+    /// Returns a uniformly distributed integer in the range.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is empty.
+    pub fn uniform(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "empty range");
+        let n = range.end - range.start;
+        // 2^64 mod n values are rejected so that the rest is a multiple of n
+        let rejected = (u64::MAX % n + 1) % n;
+        loop {
+            let x = self.next_u64();
+            if x >= rejected {
+                return range.start + x % n;
+            }
+        }
+    }
+
+    /// Shuffles the slice uniformly (Fisher–Yates).
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.uniform(0..i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Returns an index chosen with the probability proportional to its weight
+    /// (`None` if all weights are zero).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sum of weights overflows `u64`.
+    pub fn weighted_choice(&mut self, weights: &[u64]) -> Option<usize> {
+        let total = weights
+            .iter()
+            .try_fold(0_u64, |total, weight| total.checked_add(*weight))
+            .expect("sum of weights overflows u64");
+        if total == 0 {
+            return None;
+        }
+        let mut point = self.uniform(0..total);
+        weights.iter().position(|weight| {
+            if point < *weight {
+                true
+            } else {
+                point -= weight;
+                false
+            }
+        })
+    }
+}
+
+impl RngCore for DerivedRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_be_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_be_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let mut filled = 0;
+        while filled < dest.len() {
+            if self.position == self.block.len() {
+                self.block = self.next_block();
+                self.position = 0;
+            }
+            let len = (dest.len() - filled).min(self.block.len() - self.position);
+            dest[filled..filled + len]
+                .copy_from_slice(&self.block[self.position..self.position + len]);
+            filled += len;
+            self.position += len;
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vectors are computed independently (Python `hashlib`) from the scheme
+    // described in the module docs.
+
+    fn randomness() -> B512 {
+        B512::from((Bits256([1; 32]), Bits256([2; 32])))
+    }
+
+    fn bits256(hex: &str) -> Bits256 {
+        Bits256::from_hex_str(hex).unwrap()
+    }
+
+    #[test]
+    fn sub_value_vector() {
+        assert_eq!(
+            sub_value(randomness(), b"label"),
+            bits256("aa4929bae77b4d3ef043af695825f05fe3ecfa8e66636dad8d836d8050d71010")
+        );
+    }
+
+    #[test]
+    fn sub_values_vector() {
+        assert_eq!(
+            sub_values(randomness(), b"label", 2),
+            [
+                bits256("040711d9264363bfdf5659b5f0aea98baa17a49dd8dfa9f5f20f7bbe588c3a5d"),
+                bits256("8935bcb95475963f0440310b3ddbd9f3e81d63742b39ceac0c3e418346f09c61"),
+            ]
+        );
+    }
+
+    // Vectors of the `vrf_abi::derivation` Sway module (big-endian `u64`
+    // index labels), pinned by its tests as well.
+
+    #[test]
+    fn sway_sub_value_vector() {
+        assert_eq!(
+            sub_value(randomness(), &0_u64.to_be_bytes()),
+            bits256("de3850ed7f23f5e928a285518e5eafe239a17b1a967a4b1480899ddc3f967b88")
+        );
+        assert_eq!(
+            sub_value(randomness(), &1_u64.to_be_bytes()),
+            bits256("b42fb843fa20f30cbd53fed8300077633f936e486f2007a20e478bbe0d0d8e80")
+        );
+    }
+
+    #[test]
+    fn sway_sub_values_vector() {
+        assert_eq!(
+            sub_values(randomness(), &0_u64.to_be_bytes(), 2),
+            [
+                bits256("eaee4665809783a0cc8a62e1397fc1de106e6b310ffcf8c7ff6d75ca7534dfbd"),
+                bits256("b4491e30dcd1c16fdaa3e317f8fc132996d51f5bdc87627b6e0eb603f8cfcdce"),
+            ]
+        );
+    }
+
+    #[test]
+    fn sway_uniform_vector() {
+        assert_eq!(uniform(randomness(), &0_u64.to_be_bytes(), 1..7), 5);
+        assert_eq!(uniform(randomness(), &1_u64.to_be_bytes(), 0..1000), 604);
+    }
+
+    #[test]
+    fn uniform_vector() {
+        assert_eq!(uniform(randomness(), b"dice", 1..7), 2);
+    }
+
+    #[test]
+    fn uniform_rejection() {
+        // values below 2^63 - 1 are rejected, so are the first two words
+        // of the stream and the third one is used
+        let n = (1 << 63) + 1;
+        assert_eq!(
+            uniform(randomness(), b"reject-0", 0..n),
+            6365267864440357041
+        );
+        let mut rng = DerivedRng::new(randomness(), b"reject-0");
+        assert_eq!(rng.next_u64(), 4095671819771445715);
+        assert_eq!(rng.next_u64(), 2450038884168266965);
+        assert_eq!(rng.next_u64() % n, 6365267864440357041);
+    }
+
+    #[test]
+    fn uniform_power_of_two() {
+        // nothing is rejected
+        let mut rng = DerivedRng::new(randomness(), b"label");
+        let first = rng.clone().next_u64();
+        assert_eq!(rng.uniform(0..1 << 32), first % (1 << 32));
+        assert_eq!(rng.uniform(0..1), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty range")]
+    fn uniform_empty_range() {
+        uniform(randomness(), b"dice", 1..1);
+    }
+
+    #[test]
+    fn shuffle_vector() {
+        let mut items = (0..10).collect::<Vec<u8>>();
+        shuffle(randomness(), b"deck", &mut items);
+        assert_eq!(items, [1, 5, 9, 7, 4, 0, 2, 3, 8, 6]);
+
+        let mut empty: [u8; 0] = [];
+        shuffle(randomness(), b"deck", &mut empty);
+    }
+
+    #[test]
+    fn weighted_choice_vector() {
+        assert_eq!(
+            weighted_choice(randomness(), b"rarity", &[70, 25, 5]),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn weighted_choice_zero_weights() {
+        assert_eq!(weighted_choice(randomness(), b"rarity", &[]), None);
+        assert_eq!(weighted_choice(randomness(), b"rarity", &[0, 0]), None);
+        for label in [&b"a"[..], b"b", b"c", b"d"] {
+            assert_eq!(weighted_choice(randomness(), label, &[0, 3, 0]), Some(1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "sum of weights overflows u64")]
+    fn weighted_choice_overflow() {
+        weighted_choice(randomness(), b"rarity", &[u64::MAX, 1]);
+    }
+
+    #[test]
+    fn fill_bytes_across_blocks() {
+        let blocks = sub_values(randomness(), b"label", 3)
+            .iter()
+            .flat_map(|block| block.0)
+            .collect::<Vec<_>>();
+
+        let mut rng = DerivedRng::new(randomness(), b"label");
+        let mut head = [0; 5];
+        rng.fill_bytes(&mut head);
+        let mut tail = [0; 60];
+        rng.fill_bytes(&mut tail);
+        assert_eq!([&head[..], &tail[..]].concat(), blocks[..65]);
+
+        // a word that spans two blocks
+        let mut rng = DerivedRng::new(randomness(), b"label");
+        rng.fill_bytes(&mut [0; 28]);
+        assert_eq!(
+            rng.next_u64(),
+            u64::from_be_bytes(blocks[28..36].try_into().unwrap())
+        );
+        assert_eq!(
+            rng.next_u32(),
+            u32::from_be_bytes(blocks[36..40].try_into().unwrap())
+        );
+    }
+}
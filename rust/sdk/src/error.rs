@@ -1,9 +1,38 @@
 use std::io;
 
+use crate::{AccessError, ContractError, InitializationError, Unfulfilled};
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error(transparent)]
     Fuels(#[from] fuels::types::errors::Error),
     #[error(transparent)]
+    Contract(#[from] ContractError),
+    #[error(transparent)]
+    Access(#[from] AccessError),
+    #[error(transparent)]
+    Initialization(#[from] InitializationError),
+    #[error(transparent)]
     Io(#[from] io::Error),
+    #[cfg(feature = "wallet")]
+    #[error(transparent)]
+    Keystore(#[from] eth_keystore::KeystoreError),
+    #[error(
+        "fulfillment timed out with {} response(s) so far",
+        .last.as_ref().map_or(0, |x| x.keys.iter().count())
+    )]
+    Timeout { last: Option<Box<Unfulfilled>> },
+    #[error(
+        "request is reset, {} response(s) dropped",
+        .last.keys.iter().count()
+    )]
+    Reset { last: Box<Unfulfilled> },
+    #[error("cancelled")]
+    Cancelled,
+    #[error("contract {0} does not implement the VRF ABI")]
+    NotVrf(fuels::types::ContractId),
+    #[error("chain id mismatch: expected {expected}, got {actual}")]
+    ChainIdMismatch { expected: u64, actual: u64 },
+    #[error("fee {fee} plus callback fee {callback_fee} overflows u64")]
+    AmountOverflow { fee: u64, callback_fee: u64 },
 }
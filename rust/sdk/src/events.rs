@@ -0,0 +1,440 @@
+//! VRF event subscription.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use fuels::{
+    client::{PageDirection, PaginationRequest},
+    prelude::*,
+    tx::{Receipt, TxId},
+    types::{
+        transaction::{Transaction, TransactionType},
+        tx_status::TxStatus,
+        Bits256, BlockHeight, ChainId,
+    },
+};
+use futures::{stream, Stream};
+use tokio::time::sleep;
+
+use crate::{Event, Result, Vrf};
+
+/// Interval between polls for a new block once the stream reached the chain tip.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Number of transactions fetched per request by the event stream.
+pub const TRANSACTIONS_PAGE_SIZE: i32 = 50;
+
+/// Event along with its location on chain.
+pub type LocatedEvent = (BlockHeight, TxId, Event);
+
+/// Progress of a request observed in the event stream.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TrackedRequest {
+    /// Authorities responded since the request or its last reset.
+    pub responses: Vec<Address>,
+    /// Number of times the request was reset.
+    pub resets: u32,
+}
+
+/// Change of a request caused by an event (see [`RequestTracker::apply`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RequestUpdate {
+    Requested,
+    Responded {
+        authority: Address,
+    },
+    /// Collected responses are cleared and the request awaits them again.
+    Restarted {
+        dropped: Vec<Address>,
+    },
+    /// Request is fulfilled and is not tracked anymore.
+    Fulfilled,
+}
+
+/// Tracks unfulfilled requests across the event stream.
+///
+/// Requests are tracked from their first observed event, so the ones
+/// requested before the stream start are tracked as well.
+///
+/// ```no_run
+/// # use fuels::prelude::*;
+/// # use futures::StreamExt;
+/// # use orao_fuel_vrf::events::{RequestTracker, RequestUpdate};
+/// # tokio_test::block_on(async {
+/// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+/// # let from: u32 = panic!();
+/// let mut tracker = RequestTracker::default();
+/// let events = instance.events(from.into());
+/// futures::pin_mut!(events);
+/// while let Some((_, _, event)) = events.next().await.transpose()? {
+///     if let Some(RequestUpdate::Restarted { dropped }) = tracker.apply(&event) {
+///         println!("{event}: {} response(s) dropped", dropped.len());
+///     }
+/// }
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RequestTracker {
+    requests: HashMap<[u8; 32], TrackedRequest>,
+}
+
+impl RequestTracker {
+    /// Applies the event returning the change of the affected request, if any.
+    pub fn apply(&mut self, event: &Event) -> Option<RequestUpdate> {
+        match event {
+            Event::Request(request) => {
+                self.requests
+                    .insert(request.seed.0, TrackedRequest::default());
+                Some(RequestUpdate::Requested)
+            }
+            Event::Response(response) => {
+                let request = self.requests.entry(response.seed.0).or_default();
+                request.responses.push(response.authority);
+                Some(RequestUpdate::Responded {
+                    authority: response.authority,
+                })
+            }
+            Event::Reset(reset) => {
+                let request = self.requests.entry(reset.seed.0).or_default();
+                request.resets += 1;
+                Some(RequestUpdate::Restarted {
+                    dropped: std::mem::take(&mut request.responses),
+                })
+            }
+            Event::Fulfill(fulfill) => {
+                self.requests.remove(&fulfill.seed.0);
+                Some(RequestUpdate::Fulfilled)
+            }
+            Event::Callback(_) => None,
+        }
+    }
+
+    /// Returns the tracked request (`None` if it is fulfilled or never observed).
+    pub fn get(&self, seed: Bits256) -> Option<&TrackedRequest> {
+        self.requests.get(&seed.0)
+    }
+
+    /// Returns tracked unfulfilled requests.
+    pub fn pending(&self) -> impl Iterator<Item = (Bits256, &TrackedRequest)> {
+        self.requests
+            .iter()
+            .map(|(seed, request)| (Bits256(*seed), request))
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Decodes VRF events from the given receipts.
+    ///
+    /// Only the logs of this VRF instance (proxy or target contract) are considered.
+    pub fn decode_events(&self, receipts: &[Receipt]) -> Result<Vec<Event>> {
+        let receipts = receipts
+            .iter()
+            .filter(|receipt| match receipt {
+                Receipt::LogData { id, .. } => {
+                    *id == self.contract_id || Some(*id) == self.target_contract_id
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        Ok(self
+            .abi
+            .log_decoder()
+            .decode_logs_with_type::<Event>(&receipts)?)
+    }
+
+    /// Returns a stream of VRF events starting from the given block height.
+    ///
+    /// The stream tails the chain, i.e. it never ends and waits for new blocks
+    /// once it reaches the chain tip (see [`Vrf::events_with_interval`]).
+    /// Events of reverted transactions are skipped.
+    ///
+    /// Transactions are fetched along with their receipts in pages of
+    /// [`TRANSACTIONS_PAGE_SIZE`], so catching up costs a request per page
+    /// of all the chain transactions (including a mint transaction per block),
+    /// not only the VRF ones. Start from a recent block – use [`Vrf::requests`]
+    /// or [`Vrf::get_requests`] to find requests performed earlier.
+    ///
+    /// To resume after a restart, persist the height of the last processed event
+    /// and pass it as `from` – note that events of this block will be delivered again.
+    ///
+    /// ```no_run
+    /// # use fuels::prelude::*;
+    /// # use futures::StreamExt;
+    /// # tokio_test::block_on(async {
+    /// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+    /// let from = instance.abi.account().try_provider()?.latest_block_height().await?;
+    /// let events = instance.events(from.into());
+    /// futures::pin_mut!(events);
+    /// while let Some((height, tx_id, event)) = events.next().await.transpose()? {
+    ///     println!("{height} {tx_id} {event}");
+    /// }
+    /// # orao_fuel_vrf::Result::Ok(()) });
+    /// ```
+    pub fn events(&self, from: BlockHeight) -> impl Stream<Item = Result<LocatedEvent>> + '_ {
+        self.events_with_interval(from, DEFAULT_POLL_INTERVAL)
+    }
+
+    /// Same as [`Vrf::events`] but with custom poll interval.
+    pub fn events_with_interval(
+        &self,
+        from: BlockHeight,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = Result<LocatedEvent>> + '_ {
+        stream::try_unfold(
+            (Position::Block(from), VecDeque::new()),
+            move |(mut position, mut pending)| async move {
+                loop {
+                    if let Some(event) = pending.pop_front() {
+                        return Ok(Some((event, (position, pending))));
+                    }
+                    let provider = self.abi.account().try_provider()?.clone();
+                    let after = match position {
+                        Position::After(after) => after,
+                        Position::Block(height) => match start_cursor(&provider, height).await? {
+                            Some(after) => after,
+                            // the preceding block is not yet produced
+                            None => {
+                                sleep(poll_interval).await;
+                                continue;
+                            }
+                        },
+                    };
+
+                    let (transactions, next) =
+                        transactions_page(&provider, after.clone(), TRANSACTIONS_PAGE_SIZE).await?;
+                    if next == after {
+                        // reached the chain tip
+                        sleep(poll_interval).await;
+                    }
+                    for (height, tx_id, receipts) in transactions {
+                        for event in self.decode_events(&receipts)? {
+                            pending.push_back((height, tx_id, event));
+                        }
+                    }
+                    position = Position::After(next);
+                }
+            },
+        )
+    }
+}
+
+/// Position of the event stream.
+enum Position {
+    /// Starting block, the transactions cursor of which is not yet resolved.
+    Block(BlockHeight),
+    /// Transactions cursor (`None` is the chain start).
+    After(Option<String>),
+}
+
+/// Returns the transactions cursor preceding the first transaction of the given block
+/// (`None` if the preceding block is not yet produced).
+async fn start_cursor(provider: &Provider, from: BlockHeight) -> Result<Option<Option<String>>> {
+    let mut height = *from;
+    while let Some(previous) = height.checked_sub(1) {
+        let Some(block) = provider.block_by_height(previous.into()).await? else {
+            return Ok(None);
+        };
+        // the cursor is the last transaction of the preceding non-empty block
+        if let Some(tx_id) = block.transactions.last() {
+            // heights are hex-encoded in cursors
+            return Ok(Some(Some(format!(
+                "{}#{tx_id}",
+                BlockHeight::from(previous)
+            ))));
+        }
+        height = previous;
+    }
+    Ok(Some(None))
+}
+
+/// Returns successful transactions following the cursor along with the next cursor.
+async fn transactions_page(
+    provider: &Provider,
+    after: Option<String>,
+    page_size: i32,
+) -> Result<(Vec<(BlockHeight, TxId, Vec<Receipt>)>, Option<String>)> {
+    let chain_id = provider.consensus_parameters().await?.chain_id();
+    let page = provider
+        .get_transactions(PaginationRequest {
+            cursor: after.clone(),
+            results: page_size,
+            direction: PageDirection::Forward,
+        })
+        .await?;
+
+    let transactions = page
+        .results
+        .into_iter()
+        .filter_map(|response| {
+            let TxStatus::Success { receipts } = response.status else {
+                return None;
+            };
+            let tx_id = tx_id(&response.transaction, chain_id)?;
+            Some((response.block_height?, tx_id, receipts))
+        })
+        .collect();
+    // the cursor is missing if the page is empty
+    Ok((transactions, page.cursor.or(after)))
+}
+
+fn tx_id(transaction: &TransactionType, chain_id: ChainId) -> Option<TxId> {
+    Some(match transaction {
+        TransactionType::Script(tx) => tx.id(chain_id),
+        TransactionType::Create(tx) => tx.id(chain_id),
+        TransactionType::Mint(tx) => tx.id(chain_id),
+        TransactionType::Upload(tx) => tx.id(chain_id),
+        TransactionType::Upgrade(tx) => tx.id(chain_id),
+        TransactionType::Blob(tx) => tx.id(chain_id),
+        // not supported by this SDK version
+        TransactionType::Unknown => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::types::{Identity, B512};
+
+    use super::*;
+    use crate::{Fulfill, Request, Reset, Response};
+
+    const RANDOMNESS: B512 = B512 {
+        bytes: [Bits256([2; 32]), Bits256([3; 32])],
+    };
+
+    const SEED: Bits256 = Bits256([1; 32]);
+
+    fn response(authority: u8) -> Event {
+        Event::Response(Response {
+            seed: SEED,
+            authority: Address::new([authority; 32]),
+            randomness: RANDOMNESS,
+        })
+    }
+
+    #[test]
+    fn tracker_reset() {
+        let mut tracker = RequestTracker::default();
+        assert_eq!(
+            tracker.apply(&Event::Request(Request {
+                seed: SEED,
+                client: Identity::Address(Address::new([9; 32])),
+                no: 0,
+            })),
+            Some(RequestUpdate::Requested)
+        );
+        tracker.apply(&response(1));
+        tracker.apply(&response(2));
+
+        assert_eq!(
+            tracker.apply(&Event::Reset(Reset { seed: SEED })),
+            Some(RequestUpdate::Restarted {
+                dropped: vec![Address::new([1; 32]), Address::new([2; 32])],
+            })
+        );
+        let request = tracker.get(SEED).unwrap();
+        assert!(request.responses.is_empty());
+        assert_eq!(request.resets, 1);
+
+        // responses after the reset are collected anew
+        assert_eq!(
+            tracker.apply(&response(2)),
+            Some(RequestUpdate::Responded {
+                authority: Address::new([2; 32]),
+            })
+        );
+        assert_eq!(
+            tracker.apply(&Event::Reset(Reset { seed: SEED })),
+            Some(RequestUpdate::Restarted {
+                dropped: vec![Address::new([2; 32])],
+            })
+        );
+        assert_eq!(tracker.get(SEED).unwrap().resets, 2);
+
+        assert_eq!(
+            tracker.apply(&Event::Fulfill(Fulfill {
+                seed: SEED,
+                randomness: RANDOMNESS,
+            })),
+            Some(RequestUpdate::Fulfilled)
+        );
+        assert!(tracker.get(SEED).is_none());
+        assert_eq!(tracker.pending().count(), 0);
+    }
+
+    #[test]
+    fn tracker_reset_before_stream_start() {
+        // the request is performed before the stream start
+        let mut tracker = RequestTracker::default();
+        assert_eq!(
+            tracker.apply(&Event::Reset(Reset { seed: SEED })),
+            Some(RequestUpdate::Restarted { dropped: vec![] })
+        );
+        tracker.apply(&response(1));
+        let pending = tracker.pending().collect::<Vec<_>>();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].0, SEED);
+        assert_eq!(pending[0].1.responses, [Address::new([1; 32])]);
+        assert_eq!(pending[0].1.resets, 1);
+    }
+
+    #[tokio::test]
+    async fn transactions_from_block() -> Result<()> {
+        let wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new(Some(2), Some(1), Some(1_000_000)),
+            None,
+            None,
+        )
+        .await?;
+        let provider = wallets[0].try_provider()?.clone();
+        let base_asset_id = *provider.consensus_parameters().await?.base_asset_id();
+
+        let mut transfers = Vec::new();
+        for _ in 0..3 {
+            let (tx_id, _) = wallets[0]
+                .transfer(
+                    wallets[1].address(),
+                    1,
+                    base_asset_id,
+                    TxPolicies::default(),
+                )
+                .await?;
+            let height = provider
+                .get_transaction_by_id(&tx_id)
+                .await?
+                .and_then(|x| x.block_height)
+                .expect("committed transaction");
+            transfers.push((height, tx_id));
+        }
+
+        assert_eq!(start_cursor(&provider, 0.into()).await?, Some(None));
+        let tip = provider.latest_block_height().await?;
+        assert_eq!(start_cursor(&provider, (tip + 2).into()).await?, None);
+
+        // blocks span several pages of a single transaction
+        for page_size in [1, TRANSACTIONS_PAGE_SIZE] {
+            let mut after = start_cursor(&provider, transfers[1].0)
+                .await?
+                .expect("produced");
+            let mut found = Vec::new();
+            loop {
+                let (transactions, next) =
+                    transactions_page(&provider, after.clone(), page_size).await?;
+                if next == after {
+                    break;
+                }
+                found.extend(
+                    transactions
+                        .into_iter()
+                        .map(|(height, tx_id, _)| (height, tx_id))
+                        .filter(|x| transfers.contains(x)),
+                );
+                after = next;
+            }
+            assert_eq!(found, transfers[1..]);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+//! Fulfillment authority helpers.
+//!
+//! A fulfillment authority responds to a randomness request by signing its seed.
+//! The signature is the authority's share of randomness – the contract recovers
+//! the signer to authenticate the response and XORs the signature into the
+//! request state (see `Unfulfilled::fulfill` in `abi/src/randomness.sw`).
+
+use fuels::{
+    crypto::{Message, SecretKey, Signature},
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{Bits256, B512},
+};
+
+use crate::{signature_to_parts, ContractError, Error, Randomness, RandomnessState, Result, Vrf};
+
+/// Returns the message that an authority signs to fulfill the given seed.
+///
+/// The contract recovers the signer of `sha256(seed)`.
+pub fn message(seed: Bits256) -> Message {
+    Message::new(seed.0)
+}
+
+/// Signs the given seed, producing the authority's randomness response.
+pub fn sign(secret_key: &SecretKey, seed: Bits256) -> B512 {
+    signature_to_parts(Signature::sign(secret_key, &message(seed))).into()
+}
+
+/// Returns the address of an authority with the given secret key.
+pub fn authority_address(secret_key: &SecretKey) -> Address {
+    Address::new(*secret_key.public_key().hash())
+}
+
+/// Returns `true` if the error is a revert meaning that the response is not needed anymore,
+/// i.e. the authority has already responded or the request is fulfilled.
+pub fn is_benign(error: &Error) -> bool {
+    matches!(
+        error,
+        Error::Contract(ContractError::Responded | ContractError::Fulfilled)
+    )
+}
+
+/// Fulfillment authority.
+///
+/// ```no_run
+/// # use fuels::{crypto::SecretKey, prelude::*, types::Bits256};
+/// # use orao_fuel_vrf::fulfiller::Fulfiller;
+/// # tokio_test::block_on(async {
+/// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+/// # let secret_key: SecretKey = panic!();
+/// let fulfiller = Fulfiller::new(secret_key);
+/// fulfiller.fulfill(&instance, Bits256([1_u8; 32])).await?;
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug, Clone)]
+pub struct Fulfiller {
+    secret_key: SecretKey,
+    address: Address,
+}
+
+impl Fulfiller {
+    pub fn new(secret_key: SecretKey) -> Self {
+        Self {
+            address: authority_address(&secret_key),
+            secret_key,
+        }
+    }
+
+    /// Address of this authority (as listed in fulfillment authorities).
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signs the given seed.
+    pub fn sign(&self, seed: Bits256) -> B512 {
+        sign(&self.secret_key, seed)
+    }
+
+    /// Returns `true` if the request awaits the response of this authority.
+    pub fn is_pending(&self, request: &Randomness) -> bool {
+        match &request.state {
+            RandomnessState::Unfulfilled(x) => !x.keys.iter().any(|key| *key == self.address),
+            RandomnessState::Fulfilled(_) => false,
+        }
+    }
+
+    /// Signs the given seed and submits the response.
+    ///
+    /// Transaction is paid by the `instance` account, which is not required
+    /// to be the authority itself.
+    pub async fn fulfill<A: Account>(
+        &self,
+        instance: &Vrf<A>,
+        seed: Bits256,
+    ) -> Result<CallResponse<()>> {
+        self.submit(instance, seed).await
+    }
+
+    /// Same as [`Fulfiller::fulfill`] but only submits the response if the request
+    /// awaits it (see [`Fulfiller::is_pending`]).
+    ///
+    /// Returns `None` if the response is not needed, including the
+    /// [benign](is_benign) reverts caused by concurrent responses.
+    pub async fn respond<A: Account>(
+        &self,
+        instance: &Vrf<A>,
+        seed: Bits256,
+    ) -> Result<Option<CallResponse<()>>> {
+        let Some(request) = instance.get_request_by_seed(seed).await? else {
+            return Err(ContractError::UnknownRequest.into());
+        };
+        if !self.is_pending(&request) {
+            return Ok(None);
+        }
+        match self.submit(instance, seed).await {
+            Ok(response) => Ok(Some(response)),
+            Err(e) if is_benign(&e) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    // The last response doesn't execute the consumer callback,
+    // see `Vrf::execute_callback`.
+    async fn submit<A: Account>(
+        &self,
+        instance: &Vrf<A>,
+        seed: Bits256,
+    ) -> Result<CallResponse<()>> {
+        instance
+            .fulfill(seed, self.sign(seed))
+            .call()
+            .await
+            .map_err(|e| instance.decode_error(e))
+    }
+}
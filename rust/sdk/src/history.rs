@@ -0,0 +1,268 @@
+//! Paginated request history.
+
+use std::collections::VecDeque;
+
+use fuels::{prelude::*, types::Identity};
+use futures::{stream, Stream};
+
+use crate::{Randomness, RandomnessState, Result, Vrf};
+
+/// Initial number of requests fetched per page when iterating in descending order.
+///
+/// It is reduced automatically if the contract returns shorter pages.
+pub const DEFAULT_PAGE_SIZE: u64 = 10;
+
+/// Request state filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateFilter {
+    Fulfilled,
+    Unfulfilled,
+}
+
+impl StateFilter {
+    pub fn matches(&self, state: &RandomnessState) -> bool {
+        matches!(
+            (self, state),
+            (StateFilter::Fulfilled, RandomnessState::Fulfilled(_))
+                | (StateFilter::Unfulfilled, RandomnessState::Unfulfilled(_))
+        )
+    }
+}
+
+/// Query over all the performed requests.
+///
+/// ```no_run
+/// # use fuels::prelude::*;
+/// # use fuels::types::Identity;
+/// # use futures::StreamExt;
+/// # use orao_fuel_vrf::history::StateFilter;
+/// # tokio_test::block_on(async {
+/// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+/// # let address: Address = panic!();
+/// // Latest fulfilled requests of the given user
+/// let requests = instance
+///     .requests()
+///     .descending()
+///     .sender(Identity::Address(address))
+///     .state(StateFilter::Fulfilled)
+///     .stream()
+///     .take(10);
+/// futures::pin_mut!(requests);
+/// while let Some(request) = requests.next().await.transpose()? {
+///     println!("{request:?}");
+/// }
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug)]
+pub struct RequestsQuery<'a, A: Account> {
+    instance: &'a Vrf<A>,
+    descending: bool,
+    page_size: u64,
+    sender: Option<Identity>,
+    state: Option<StateFilter>,
+}
+
+impl<'a, A: Account> RequestsQuery<'a, A> {
+    pub(crate) fn new(instance: &'a Vrf<A>) -> Self {
+        Self {
+            instance,
+            descending: false,
+            page_size: DEFAULT_PAGE_SIZE,
+            sender: None,
+            state: None,
+        }
+    }
+
+    /// Iterate from the latest request to the first one.
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// See [`DEFAULT_PAGE_SIZE`].
+    pub fn page_size(mut self, page_size: u64) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Only requests of the given sender.
+    pub fn sender(mut self, sender: Identity) -> Self {
+        self.sender = Some(sender);
+        self
+    }
+
+    /// Only requests in the given state.
+    pub fn state(mut self, state: StateFilter) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    fn matches(&self, request: &Randomness) -> bool {
+        self.sender.map_or(true, |x| x == request.sender)
+            && self.state.map_or(true, |x| x.matches(&request.state))
+    }
+
+    /// Returns a stream of matching requests.
+    pub fn stream(self) -> impl Stream<Item = Result<Randomness>> + 'a {
+        stream::try_unfold(
+            (self, None, VecDeque::new()),
+            |(mut query, mut cursor, mut pending)| async move {
+                loop {
+                    if let Some(request) = pending.pop_front() {
+                        if query.matches(&request) {
+                            return Ok(Some((request, (query, cursor, pending))));
+                        }
+                        continue;
+                    }
+
+                    let page = if query.descending {
+                        // cursor is the end of the remaining range
+                        let end = match cursor {
+                            Some(end) => end,
+                            None => query.instance.get_num_requests().await?,
+                        };
+                        if end == 0 {
+                            return Ok(None);
+                        }
+                        let offset = end.saturating_sub(query.page_size);
+                        let mut page = query.instance.get_requests(offset).await?;
+                        if (page.len() as u64) < end - offset {
+                            // contract pages are shorter than requested
+                            if page.is_empty() {
+                                return Ok(None);
+                            }
+                            query.page_size = page.len() as u64;
+                            cursor = Some(end);
+                            continue;
+                        }
+                        page.truncate((end - offset) as usize);
+                        page.reverse();
+                        cursor = Some(offset);
+                        page
+                    } else {
+                        // cursor is the next offset
+                        let offset = cursor.unwrap_or(0);
+                        let page = query.instance.get_requests(offset).await?;
+                        if page.is_empty() {
+                            return Ok(None);
+                        }
+                        cursor = Some(offset + page.len() as u64);
+                        page
+                    };
+                    pending = page.into();
+                }
+            },
+        )
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Returns a query over all the performed requests.
+    ///
+    /// Requests are fetched page by page using [`Vrf::get_requests`].
+    pub fn requests(&self) -> RequestsQuery<'_, A> {
+        RequestsQuery::new(self)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use fuels::types::Bits256;
+    use futures::TryStreamExt;
+
+    use super::*;
+    use crate::testing::{TestConfig, TestEnv};
+
+    /// More requests than a contract page (24 requests).
+    const NUM_REQUESTS: u8 = 26;
+
+    /// Performs requests with seeds `[i; 32]` for `i` in `1..=NUM_REQUESTS`:
+    /// odd ones by the client, even ones by the authority, every fifth is fulfilled.
+    async fn setup() -> Result<(TestEnv, Vec<Bits256>)> {
+        let env = TestEnv::launch_with(TestConfig {
+            num_authorities: 1,
+            ..TestConfig::default()
+        })
+        .await?;
+        let mut seeds = Vec::new();
+        for i in 1..=NUM_REQUESTS {
+            let seed = Bits256([i; 32]);
+            if i % 2 == 1 {
+                env.request(seed).await?;
+            } else {
+                env.vrf.request_builder(seed).send().await?;
+            }
+            if i % 5 == 0 {
+                env.fulfill(seed).await?;
+            }
+            seeds.push(seed);
+        }
+        Ok((env, seeds))
+    }
+
+    async fn seeds<A: Account>(query: RequestsQuery<'_, A>) -> Result<Vec<Bits256>> {
+        query
+            .stream()
+            .map_ok(|request| request.seed)
+            .try_collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn ascending() -> Result<()> {
+        let (env, expected) = setup().await?;
+        assert_eq!(seeds(env.vrf.requests()).await?, expected);
+        // the page size only applies to the descending order
+        assert_eq!(seeds(env.vrf.requests().page_size(3)).await?, expected);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn descending() -> Result<()> {
+        let (env, mut expected) = setup().await?;
+        expected.reverse();
+        // a page size that doesn't divide the number of requests,
+        // the contract page size and a larger one that is reduced to it
+        for page_size in [3, 24, 100] {
+            let query = env.vrf.requests().descending().page_size(page_size);
+            assert_eq!(seeds(query).await?, expected, "page size {page_size}");
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn filters() -> Result<()> {
+        let (env, all) = setup().await?;
+        let client = Identity::Address(env.client.address().into());
+        let by = |filter: fn(u8) -> bool| {
+            all.iter()
+                .copied()
+                .filter(|seed| filter(seed.0[0]))
+                .collect::<Vec<_>>()
+        };
+
+        let query = env.vrf.requests().sender(client);
+        assert_eq!(seeds(query).await?, by(|i| i % 2 == 1));
+
+        let query = env.vrf.requests().state(StateFilter::Fulfilled);
+        assert_eq!(seeds(query).await?, by(|i| i % 5 == 0));
+
+        let query = env
+            .vrf
+            .requests()
+            .descending()
+            .page_size(4)
+            .sender(client)
+            .state(StateFilter::Unfulfilled);
+        let mut expected = by(|i| i % 2 == 1 && i % 5 != 0);
+        expected.reverse();
+        assert_eq!(seeds(query).await?, expected);
+
+        let query = env
+            .vrf
+            .requests()
+            .sender(Identity::ContractId(env.consumer));
+        assert!(seeds(query).await?.is_empty());
+        Ok(())
+    }
+}
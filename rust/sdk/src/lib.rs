@@ -2,26 +2,55 @@ use std::fmt::Debug;
 
 pub use abi::{
     bindings::{
-        Error as ContractError, Event, Fulfill, Fulfilled, Randomness, RandomnessState, Request,
-        Reset, Response, State, Unfulfilled,
+        AccessError, Callback, Error as ContractError, Event, Fulfill, Fulfilled,
+        InitializationError, Randomness, RandomnessState, Request, Reset, Response, State,
+        Unfulfilled,
     },
-    randomness_to_bytes64,
+    bytes64_to_randomness, randomness_to_bytes64,
 };
 pub use error::Error;
 use fuels::{
     core::traits::{Parameterize, Tokenizable},
     crypto::Signature,
     prelude::*,
-    programs::calls::{CallHandler, ContractCall},
-    types::{Bits256, Identity},
+    programs::{
+        calls::{CallHandler, ContractCall},
+        responses::CallResponse,
+    },
+    types::{errors::transaction::Reason, Bits256, Identity, B512},
 };
+pub use network::Network;
+pub use proxy::Proxy;
 
 pub mod abi;
+pub mod batch;
+pub mod derive;
 pub mod error;
+pub mod events;
+pub mod fulfiller;
+pub mod history;
+#[cfg(feature = "testing")]
+pub mod mock;
+pub mod monitor;
+pub mod network;
+pub mod proxy;
+pub mod request;
+pub mod seed;
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod verify;
+pub mod wait;
+#[cfg(feature = "wallet")]
+pub mod wallet;
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 pub const MAX_AUTHORITIES: usize = 10;
+
+/// Script gas limit of read-only multi-calls.
+pub const MULTI_CALL_GAS_LIMIT: u64 = 10_000_000;
 pub const MAINNET_CONTRACT_ID: ContractId = ContractId::new([
     0xf0, 0xb0, 0xfc, 0xde, 0xd2, 0xb3, 0xdc, 0xbc, 0x52, 0x9d, 0x61, 0x13, 0x00, 0xb9, 0x04, 0xdf,
     0x97, 0xbf, 0x47, 0x32, 0x40, 0xce, 0x46, 0x79, 0x99, 0x3e, 0x41, 0x8b, 0x36, 0xb3, 0xe8, 0xd0,
@@ -40,24 +69,51 @@ pub struct Vrf<T: Account> {
 }
 
 impl<A: Account> Vrf<A> {
+    /// Creates an instance of the VRF contract, which is either the proxy
+    /// or the VRF contract used directly.
+    ///
+    /// Fails if it is unable to find out whether the contract is a proxy
+    /// (e.g. on a transport error).
+    pub async fn try_new(contract_id: ContractId, wallet: A) -> Result<Self> {
+        let abi = abi::bindings::Vrf::new(contract_id, wallet);
+        let proxy = Proxy::new(
+            contract_id,
+            ImpersonatedAccount::new(
+                Bech32Address::default(),
+                Some(abi.account().try_provider()?.clone()),
+            ),
+        );
+        let target_contract_id = match proxy.target().await {
+            Ok(target_contract_id) => target_contract_id,
+            // Contract without `proxy_target` reverts on the unmatched selector,
+            // which means that the VRF contract is used directly.
+            Err(Error::Fuels(fuels::types::errors::Error::Transaction(Reason::Reverted {
+                ..
+            }))) => None,
+            Err(e) => return Err(e),
+        };
+
+        Ok(Self {
+            contract_id,
+            target_contract_id,
+            methods: abi.methods(),
+            abi,
+        })
+    }
+
+    /// Same as [`Vrf::try_new`] but treats any failure to query the proxy target
+    /// as the VRF contract being used directly.
+    #[deprecated(note = "use `Vrf::try_new`, which doesn't swallow query errors")]
     pub async fn new(contract_id: ContractId, wallet: A) -> Self {
         let abi = abi::bindings::Vrf::new(contract_id, wallet);
-        let proxy_abi = abi::bindings::Proxy::new(
+        let proxy = Proxy::new(
             contract_id,
             ImpersonatedAccount::new(
                 Bech32Address::default(),
                 Some(abi.account().try_provider().unwrap().clone()),
             ),
         );
-        let target_contract_id = match proxy_abi
-            .methods()
-            .proxy_target()
-            .simulate(Execution::StateReadOnly)
-            .await
-        {
-            Ok(result) => result.value,
-            Err(_) => None,
-        };
+        let target_contract_id = proxy.target().await.unwrap_or(None);
 
         Self {
             contract_id,
@@ -67,6 +123,23 @@ impl<A: Account> Vrf<A> {
         }
     }
 
+    /// Returns this instance bound to another account
+    /// (the proxy target is not queried again).
+    pub fn with_account<B: Account>(&self, account: B) -> Vrf<B> {
+        let abi = abi::bindings::Vrf::new(self.contract_id, account);
+        Vrf {
+            contract_id: self.contract_id,
+            target_contract_id: self.target_contract_id,
+            methods: abi.methods(),
+            abi,
+        }
+    }
+
+    /// Returns the handle of the proxy this instance is called through.
+    pub fn proxy(&self) -> Proxy<A> {
+        Proxy::new(self.contract_id, self.abi.account())
+    }
+
     pub fn contract_ids(&self) -> Vec<Bech32ContractId> {
         let mut ids = vec![self.contract_id.into()];
         if let Some(target_id) = self.target_contract_id {
@@ -85,12 +158,50 @@ impl<A: Account> Vrf<A> {
         call
     }
 
+    /// Converts the given error into a typed error if it is a contract revert.
+    ///
+    /// Errors returned by `Vrf` methods are already converted, use this
+    /// with call handlers returned by [`Vrf::request`] or [`Vrf::fulfill`].
+    ///
+    /// ```no_run
+    /// # use fuels::prelude::*;
+    /// # use fuels::types::Bits256;
+    /// # use orao_fuel_vrf::{ContractError, Error};
+    /// # tokio_test::block_on(async {
+    /// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+    /// let result = instance.request(Bits256([1_u8; 32])).call().await;
+    /// match result.map_err(|e| instance.decode_error(e)) {
+    ///     Err(Error::Contract(ContractError::SeedInUse)) => eprintln!("Seed is in use"),
+    ///     x => println!("{:?}", x?.value),
+    /// }
+    /// # orao_fuel_vrf::Result::Ok(()) });
+    /// ```
+    pub fn decode_error(&self, error: fuels::types::errors::Error) -> Error {
+        let fuels::types::errors::Error::Transaction(Reason::Reverted { ref receipts, .. }) = error
+        else {
+            return error.into();
+        };
+
+        let log_decoder = self.abi.log_decoder();
+        if let Some(x) = last_log::<ContractError>(&log_decoder, receipts) {
+            Error::Contract(x)
+        } else if let Some(x) = last_log::<AccessError>(&log_decoder, receipts) {
+            Error::Access(x)
+        } else if let Some(x) = last_log::<InitializationError>(&log_decoder, receipts) {
+            Error::Initialization(x)
+        } else {
+            error.into()
+        }
+    }
+
     /// Performs the randomness request.
     ///
     /// Client is able to pay fees with the base asset or with an additional asset,
     /// if it is configured and enabled. Transfer amount must be equal to the fee,
     /// configured for the asset being used.
     ///
+    /// See [`Vrf::request_builder`] for a helper that picks the asset automatically.
+    ///
     /// ```no_run
     /// # use fuels::prelude::*;
     /// # use fuels::types::Bits256;
@@ -128,6 +239,118 @@ impl<A: Account> Vrf<A> {
         self.with_target_contract(self.methods.request(seed))
     }
 
+    /// Submits a fulfillment authority response for the given seed.
+    ///
+    /// `signature` is the authority's signature of the seed
+    /// (see [`fulfiller`] for helpers that produce it).
+    pub fn fulfill(&self, seed: Bits256, signature: B512) -> CallHandler<A, ContractCall, ()> {
+        self.with_target_contract(self.methods.fulfill(seed, signature))
+    }
+
+    /// Executes the consumer callback of a fulfilled request.
+    ///
+    /// The VRF doesn't execute callbacks on fulfillment – the consumer contract
+    /// that made the request calls `execute_callback` itself, any other caller
+    /// gets [`AccessError::NotAuthorized`]. The callback is executed once and only
+    /// if the request has a non-zero callback fee, otherwise the call is a no-op.
+    ///
+    /// So this is only useful with contracts that don't check the caller,
+    /// e.g. the [mock VRF](crate::mock).
+    pub async fn execute_callback(&self, seed: Bits256) -> Result<CallResponse<()>> {
+        let mut contract_ids = self.contract_ids();
+        if let Some(Randomness {
+            sender: Identity::ContractId(client),
+            ..
+        }) = self.get_request_by_seed(seed).await?
+        {
+            contract_ids.push(client.into());
+        }
+
+        self.methods
+            .execute_callback(seed)
+            .with_contract_ids(&contract_ids)
+            // the consumer callback may transfer assets
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .map_err(|e| self.decode_error(e))
+    }
+
+    /// Configures the contract instance.
+    ///
+    /// Sets the authority, the base asset fee and the list of fulfillment authorities.
+    /// Must be called by the current authority (the first call initializes the ownership).
+    ///
+    /// Arguments are validated client-side, so that an obviously invalid configuration
+    /// fails with [`Error::Contract`] instead of a reverted transaction.
+    pub async fn configure(
+        &self,
+        authority: Identity,
+        fee: u64,
+        fulfillment_authorities: Vec<Address>,
+    ) -> Result<CallResponse<()>> {
+        if is_zero_identity(&authority) || fulfillment_authorities.contains(&Address::zeroed()) {
+            return Err(ContractError::ZeroAuthority.into());
+        }
+        if fee == 0 {
+            return Err(ContractError::ZeroFee.into());
+        }
+
+        self.with_target_contract(
+            self.methods
+                .configure(authority, fee, fulfillment_authorities),
+        )
+        .call()
+        .await
+        .map_err(|e| self.decode_error(e))
+    }
+
+    /// Configures the additional asset to pay fees with.
+    ///
+    /// Use zero `fee` to disable the additional asset, or pass the base asset
+    /// with zero `fee` to remove it. Fees collected in the previously configured
+    /// asset must be withdrawn before changing the asset.
+    pub async fn configure_asset(&self, asset: AssetId, fee: u64) -> Result<CallResponse<()>> {
+        let base_asset_id = self.base_asset_id().await?;
+        if asset == base_asset_id && fee != 0 {
+            return Err(ContractError::NonZeroFee.into());
+        }
+
+        let current_asset = self.get_asset().await?;
+        if current_asset != base_asset_id
+            && current_asset != asset
+            && self.get_balance(current_asset).await? > 0
+        {
+            return Err(ContractError::RemainingAssets.into());
+        }
+
+        self.with_target_contract(self.methods.configure_asset(asset, fee))
+            .call()
+            .await
+            .map_err(|e| self.decode_error(e))
+    }
+
+    /// Withdraws collected fees of the given asset to the `recipient`.
+    pub async fn withdraw_fees(
+        &self,
+        asset: AssetId,
+        amount: u64,
+        recipient: Address,
+    ) -> Result<CallResponse<()>> {
+        if amount == 0 {
+            return Err(ContractError::NoAmountSpecified.into());
+        }
+        if amount > self.get_balance(asset).await? {
+            return Err(ContractError::NotEnoughFunds.into());
+        }
+
+        self.with_target_contract(self.methods.withdraw_fees(asset, amount, recipient))
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .map_err(|e| self.decode_error(e))
+    }
+
     /// Returns the configured authority.
     ///
     /// # Note
@@ -137,7 +360,8 @@ impl<A: Account> Vrf<A> {
         match self
             .with_target_contract(self.methods.owner())
             .simulate(Execution::StateReadOnly)
-            .await?
+            .await
+            .map_err(|e| self.decode_error(e))?
             .value
         {
             State::Initialized(authority) => Ok(Some(authority)),
@@ -152,7 +376,8 @@ impl<A: Account> Vrf<A> {
         Ok(self
             .with_target_contract(self.methods.get_fee(asset))
             .simulate(Execution::StateReadOnly)
-            .await?
+            .await
+            .map_err(|e| self.decode_error(e))?
             .value)
     }
 
@@ -163,7 +388,8 @@ impl<A: Account> Vrf<A> {
         Ok(AssetId::new(
             self.with_target_contract(self.methods.get_asset())
                 .simulate(Execution::StateReadOnly)
-                .await?
+                .await
+                .map_err(|e| self.decode_error(e))?
                 .value
                 .into(),
         ))
@@ -174,7 +400,8 @@ impl<A: Account> Vrf<A> {
         let response = self
             .with_target_contract(self.methods.get_fulfillment_authorities())
             .simulate(Execution::StateReadOnly)
-            .await?;
+            .await
+            .map_err(|e| self.decode_error(e))?;
         Ok(response.value)
     }
 
@@ -183,7 +410,8 @@ impl<A: Account> Vrf<A> {
         Ok(self
             .with_target_contract(self.methods.get_balance(asset))
             .simulate(Execution::StateReadOnly)
-            .await?
+            .await
+            .map_err(|e| self.decode_error(e))?
             .value)
     }
 
@@ -192,7 +420,8 @@ impl<A: Account> Vrf<A> {
         let response = self
             .with_target_contract(self.methods.get_request_by_num(num))
             .simulate(Execution::StateReadOnly)
-            .await?;
+            .await
+            .map_err(|e| self.decode_error(e))?;
         Ok(response.value)
     }
 
@@ -201,7 +430,18 @@ impl<A: Account> Vrf<A> {
         let response = self
             .with_target_contract(self.methods.get_request_by_seed(seed))
             .simulate(Execution::StateReadOnly)
-            .await?;
+            .await
+            .map_err(|e| self.decode_error(e))?;
+        Ok(response.value)
+    }
+
+    /// Returns a page of requests starting at the given offset.
+    pub async fn get_requests(&self, offset: u64) -> Result<Vec<Randomness>> {
+        let response = self
+            .with_target_contract(self.methods.get_requests(offset))
+            .simulate(Execution::StateReadOnly)
+            .await
+            .map_err(|e| self.decode_error(e))?;
         Ok(response.value)
     }
 
@@ -210,42 +450,50 @@ impl<A: Account> Vrf<A> {
         Ok(self
             .with_target_contract(self.methods.get_num_requests())
             .simulate(Execution::StateReadOnly)
-            .await?
+            .await
+            .map_err(|e| self.decode_error(e))?
             .value)
     }
 
-    /// Convenience method that returns on-chain VRF status.
-    // TODO: Clean this up as soon as FuelLabs/fuels-rs#914 is fixed
-    // TODO: 15.03.2024. this should be refactored using ReceiptParser
-    pub async fn get_status(&self) -> Result<Status> {
+    /// Returns the base asset id of the connected network.
+    pub async fn base_asset_id(&self) -> Result<AssetId> {
         let consensus_parameters = self
             .abi
             .account()
             .try_provider()?
             .consensus_parameters()
             .await?;
-        let base_asset_id = consensus_parameters.base_asset_id();
+        Ok(*consensus_parameters.base_asset_id())
+    }
+
+    /// Convenience method that returns on-chain VRF status.
+    // TODO: Clean this up as soon as FuelLabs/fuels-rs#914 is fixed
+    // TODO: 15.03.2024. this should be refactored using ReceiptParser
+    pub async fn get_status(&self) -> Result<Status> {
+        let base_asset_id = self.base_asset_id().await?;
         let mut call = CallHandler::new_multi_call(self.abi.account())
             .add_call(self.with_target_contract(self.methods.owner()))
-            .add_call(self.with_target_contract(self.methods.get_balance(base_asset_id.clone())))
-            .add_call(self.with_target_contract(self.methods.get_fee(base_asset_id.clone())))
+            .add_call(self.with_target_contract(self.methods.get_balance(base_asset_id)))
+            .add_call(self.with_target_contract(self.methods.get_fee(base_asset_id)))
             .add_call(self.with_target_contract(self.methods.get_asset()))
             .add_call(self.with_target_contract(self.methods.get_fulfillment_authorities()))
             .add_call(self.with_target_contract(self.methods.get_num_requests()))
-            .with_tx_policies(TxPolicies::default().with_script_gas_limit(10_000_000));
+            .with_tx_policies(TxPolicies::default().with_script_gas_limit(MULTI_CALL_GAS_LIMIT));
 
         let response = call
             .simulate::<(State, u64, u64, AssetId, Vec<Address>, u64)>(Execution::StateReadOnly)
-            .await?;
+            .await
+            .map_err(|e| self.decode_error(e))?;
         let asset = response.value.3;
 
-        let additional_asset = if asset != *base_asset_id {
+        let additional_asset = if asset != base_asset_id {
             let mut call = CallHandler::new_multi_call(self.abi.account())
                 .add_call(self.with_target_contract(self.methods.get_balance(asset)))
                 .add_call(self.with_target_contract(self.methods.get_fee(asset)));
             let response = call
                 .simulate::<(u64, u64)>(Execution::StateReadOnly)
-                .await?;
+                .await
+                .map_err(|e| self.decode_error(e))?;
             Some((
                 asset,
                 AssetStatus {
@@ -276,6 +524,7 @@ impl<A: Account> Vrf<A> {
 
 /// Structure that represents on-chain VRF state.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Status {
     pub authority: Option<Identity>,
     pub num_requests: u64,
@@ -285,11 +534,29 @@ pub struct Status {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AssetStatus {
     pub fee: u64,
     pub balance: u64,
 }
 
+pub(crate) fn last_log<T: Tokenizable + Parameterize + 'static>(
+    log_decoder: &LogDecoder,
+    receipts: &[Receipt],
+) -> Option<T> {
+    log_decoder
+        .decode_logs_with_type::<T>(receipts)
+        .ok()
+        .and_then(|mut logs| logs.pop())
+}
+
+fn is_zero_identity(identity: &Identity) -> bool {
+    match identity {
+        Identity::Address(x) => *x == Address::zeroed(),
+        Identity::ContractId(x) => *x == ContractId::zeroed(),
+    }
+}
+
 pub fn signature_to_parts(s: Signature) -> (Bits256, Bits256) {
     let mut fst = [0_u8; Signature::LEN / 2];
     fst.copy_from_slice(&s[..Signature::LEN / 2]);
@@ -297,3 +564,32 @@ pub fn signature_to_parts(s: Signature) -> (Bits256, Bits256) {
     snd.copy_from_slice(&s[Signature::LEN / 2..]);
     (Bits256(fst), Bits256(snd))
 }
+
+#[cfg(test)]
+mod tests {
+    use fuel_asm::op;
+    use fuels::types::Salt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn try_new_direct() -> Result<()> {
+        let wallet = launch_provider_and_get_wallet().await?;
+        // reverts on any call, like a contract without the called function
+        let code = [op::movi(0x10, 123), op::rvrt(0x10)]
+            .into_iter()
+            .collect::<Vec<u8>>();
+        let contract_id = Contract::regular(code, Salt::zeroed(), vec![])
+            .deploy(&wallet, TxPolicies::default())
+            .await?;
+
+        let instance = Vrf::try_new((&contract_id).into(), wallet.clone()).await?;
+        assert_eq!(instance.target_contract_id, None);
+
+        // unable to query a missing contract
+        assert!(Vrf::try_new(ContractId::new([1; 32]), wallet)
+            .await
+            .is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+//! Mock VRF for consumer tests (requires the `testing` feature).
+//!
+//! The mock contract (`rust/sdk/mock`) implements the `Vrf` ABI without
+//! fulfillment authorities – requests are fulfilled instantly with randomness
+//! chosen by the test via `mock_fulfill`, which invokes the consumer callback
+//! right away (the VRF only executes it when the consumer calls
+//! `execute_callback`, see [`Vrf::execute_callback`]).
+//!
+//! Consumers authenticate the VRF by its contract id, so deploy the consumer
+//! configured with [`MockVrf::contract_id`].
+//!
+//! ```no_run
+//! # use fuels::{prelude::*, types::Bits256};
+//! # use orao_fuel_vrf::mock::MockVrf;
+//! # tokio_test::block_on(async {
+//! let wallet = launch_provider_and_get_wallet().await?;
+//! let mock = MockVrf::deploy_from_env(wallet).await?;
+//! // ... a consumer performs a request with this seed
+//! let seed = Bits256([1_u8; 32]);
+//! let randomness = mock.fulfill_random(seed).await?;
+//! # orao_fuel_vrf::Result::Ok(()) });
+//! ```
+
+use std::{env, io, path::Path};
+
+use fuels::{
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{Bits256, Identity, B512},
+};
+
+use crate::{abi::bindings::VrfMock, Result, Vrf};
+
+/// Environment variable with the path to the mock VRF contract binary.
+pub const MOCK_BINARY_ENV: &str = "ORAO_FUEL_VRF_MOCK_BINARY";
+
+/// Deployed mock VRF.
+#[derive(Debug)]
+pub struct MockVrf<A: Account> {
+    pub contract_id: ContractId,
+    pub abi: VrfMock<A>,
+    /// SDK instance over the mock (only the `Vrf` ABI methods are supported).
+    pub vrf: Vrf<A>,
+}
+
+impl<A: Account + Clone> MockVrf<A> {
+    pub async fn new(contract_id: ContractId, wallet: A) -> Result<Self> {
+        Ok(Self {
+            contract_id,
+            abi: VrfMock::new(contract_id, wallet.clone()),
+            vrf: Vrf::try_new(contract_id, wallet).await?,
+        })
+    }
+
+    /// Deploys the mock from the given binary (the storage slots file next to it
+    /// is loaded automatically).
+    pub async fn deploy(binary: impl AsRef<Path>, wallet: A) -> Result<Self> {
+        let contract_id = Contract::load_from(binary, LoadConfiguration::default())?
+            .deploy(&wallet, TxPolicies::default())
+            .await?;
+        Self::new((&contract_id).into(), wallet).await
+    }
+
+    /// Deploys the mock from the binary at [`MOCK_BINARY_ENV`].
+    pub async fn deploy_from_env(wallet: A) -> Result<Self> {
+        let binary = env::var_os(MOCK_BINARY_ENV).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{MOCK_BINARY_ENV} is not set"),
+            )
+        })?;
+        Self::deploy(binary, wallet).await
+    }
+
+    /// Sets the base asset fee (zero by default).
+    pub async fn set_fee(&self, fee: u64) -> Result<CallResponse<()>> {
+        self.abi
+            .methods()
+            .mock_set_fee(fee)
+            .call()
+            .await
+            .map_err(|e| self.vrf.decode_error(e))
+    }
+
+    /// Fulfills the request with the given randomness invoking the consumer callback.
+    pub async fn fulfill(&self, seed: Bits256, randomness: B512) -> Result<CallResponse<()>> {
+        let mut call = self.abi.methods().mock_fulfill(seed, randomness);
+        if let Some(request) = self.vrf.get_request_by_seed(seed).await? {
+            if let Identity::ContractId(client) = request.sender {
+                call = call
+                    .with_contract_ids(&[client.into()])
+                    // the consumer callback may transfer assets
+                    .with_variable_output_policy(VariableOutputPolicy::Exactly(1));
+            }
+        }
+        call.call().await.map_err(|e| self.vrf.decode_error(e))
+    }
+
+    /// Fulfills the request with random randomness and returns it.
+    pub async fn fulfill_random(&self, seed: Bits256) -> Result<B512> {
+        let randomness = B512::from((Bits256(rand::random()), Bits256(rand::random())));
+        self.fulfill(seed, randomness).await?;
+        Ok(randomness)
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::{testing::TestEnv, RandomnessState};
+
+    /// Returns the ABI functions by name along with the logged types.
+    fn abi(
+        json: &str,
+    ) -> (
+        serde_json::Map<String, serde_json::Value>,
+        serde_json::Value,
+    ) {
+        let abi = serde_json::from_str::<serde_json::Value>(json).unwrap();
+        let functions = abi["functions"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|function| {
+                (
+                    function["name"].as_str().unwrap().to_owned(),
+                    function.clone(),
+                )
+            })
+            .collect();
+        (functions, abi["loggedTypes"].clone())
+    }
+
+    // The mock ABI must match the VRF one, so that `MockVrf::vrf` calls
+    // and `Vrf::decode_error` work against the mock.
+    #[test]
+    fn abi_matches_vrf() {
+        let (mock_functions, mock_logs) = abi(include_str!("../abi/mock.json"));
+        let (vrf_functions, vrf_logs) = abi(include_str!("../abi/vrf.json"));
+        for (name, function) in &mock_functions {
+            if !name.starts_with("mock_") {
+                assert_eq!(Some(function), vrf_functions.get(name), "{name}");
+            }
+        }
+        // errors are logged the same way
+        let error_log = |logs: &serde_json::Value| {
+            logs.as_array()
+                .unwrap()
+                .iter()
+                .find(|log| log["logId"] == mock_logs[0]["logId"])
+                .cloned()
+        };
+        assert_eq!(mock_logs.as_array().unwrap().len(), 1);
+        assert_eq!(error_log(&vrf_logs), error_log(&mock_logs));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires the `forc build` output of `rust/sdk/mock` at `ORAO_FUEL_VRF_MOCK_BINARY`"]
+    async fn fulfill_callback() -> Result<()> {
+        let env = TestEnv::launch().await?;
+        let mock = MockVrf::deploy_from_env(env.client.clone()).await?;
+        mock.set_fee(10).await?;
+        let base_asset_id = *env.provider.consensus_parameters().await?.base_asset_id();
+        env.client
+            .force_transfer_to_contract(
+                &env.consumer.into(),
+                1,
+                base_asset_id,
+                TxPolicies::default(),
+            )
+            .await?;
+
+        let seed = Bits256([3; 32]);
+        env.consumer_call(&mock.vrf, "request", seed)
+            .call_params(CallParameters::default().with_amount(10))?
+            .call()
+            .await?;
+        let request = mock.vrf.get_request_by_seed(seed).await?.expect("request");
+        assert_eq!(request.sender, Identity::ContractId(env.consumer));
+
+        let randomness = mock.fulfill_random(seed).await?;
+        let request = mock.vrf.get_request_by_seed(seed).await?.expect("request");
+        assert!(matches!(
+            request.state,
+            RandomnessState::Fulfilled(fulfilled) if fulfilled.randomness == randomness
+        ));
+        // the callback has transferred a coin to the address equal to the seed
+        let balance = env
+            .provider
+            .get_asset_balance(&Address::new(seed.0).into(), base_asset_id)
+            .await?;
+        assert_eq!(balance, 1);
+
+        assert!(mock.fulfill(seed, randomness).await.is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,442 @@
+//! VRF status monitoring.
+
+use std::{collections::VecDeque, fmt, time::Duration};
+
+use fuels::{prelude::*, types::Identity};
+use futures::{stream, Stream};
+use tokio::time::sleep;
+
+use crate::{Result, Status, Vrf};
+
+/// Change between two consecutive [`Status`] snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StatusChange {
+    AuthorityChanged {
+        old: Option<Identity>,
+        new: Option<Identity>,
+    },
+    FulfillmentAuthoritiesChanged {
+        added: Vec<Address>,
+        removed: Vec<Address>,
+    },
+    /// Additional asset is configured (`old` is `None`), replaced or unset by configuring
+    /// the base asset (`new` is `None`).
+    ///
+    /// Disabling the additional asset with a zero fee keeps it configured, so it's reported
+    /// as [`StatusChange::FeeChanged`] with the `new` fee of zero.
+    AdditionalAssetChanged {
+        old: Option<AssetId>,
+        new: Option<AssetId>,
+    },
+    FeeChanged {
+        asset: AssetId,
+        old: u64,
+        new: u64,
+    },
+    /// Balance of the asset crossed the configured threshold.
+    BalanceCrossedThreshold {
+        asset: AssetId,
+        threshold: u64,
+        balance: u64,
+    },
+    RequestsAdvanced {
+        old: u64,
+        new: u64,
+    },
+}
+
+impl fmt::Display for StatusChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StatusChange::AuthorityChanged { old, new } => {
+                write!(f, "Authority changed: {old:?} -> {new:?}")
+            }
+            StatusChange::FulfillmentAuthoritiesChanged { added, removed } => write!(
+                f,
+                "Fulfillment authorities changed: added={added:?}, removed={removed:?}"
+            ),
+            StatusChange::AdditionalAssetChanged { old, new } => {
+                write!(f, "Additional asset changed: {old:?} -> {new:?}")
+            }
+            StatusChange::FeeChanged { asset, old, new } => {
+                write!(f, "Fee changed: asset={asset}, {old} -> {new}")
+            }
+            StatusChange::BalanceCrossedThreshold {
+                asset,
+                threshold,
+                balance,
+            } => {
+                let direction = if balance >= threshold {
+                    "above"
+                } else {
+                    "below"
+                };
+                write!(
+                    f,
+                    "Balance is {direction} the threshold: asset={asset}, threshold={threshold}, balance={balance}"
+                )
+            }
+            StatusChange::RequestsAdvanced { old, new } => {
+                write!(f, "Requests advanced: {old} -> {new}")
+            }
+        }
+    }
+}
+
+impl Status {
+    /// Returns the fee and the balance of the given asset
+    /// (`None` if the asset is not configured).
+    pub fn asset(&self, asset: AssetId, base_asset_id: AssetId) -> Option<crate::AssetStatus> {
+        if asset == base_asset_id {
+            return Some(self.base_asset);
+        }
+        self.additional_asset
+            .filter(|(additional_asset, _)| *additional_asset == asset)
+            .map(|(_, status)| status)
+    }
+
+    /// Returns changes from `self` to the `new` status.
+    ///
+    /// `balance_thresholds` are checked for crossing in either direction.
+    pub fn diff(
+        &self,
+        new: &Status,
+        base_asset_id: AssetId,
+        balance_thresholds: &[(AssetId, u64)],
+    ) -> Vec<StatusChange> {
+        let mut changes = Vec::new();
+
+        if self.authority != new.authority {
+            changes.push(StatusChange::AuthorityChanged {
+                old: self.authority,
+                new: new.authority,
+            });
+        }
+
+        let added = new
+            .fulfillment_authorities
+            .iter()
+            .filter(|x| !self.fulfillment_authorities.contains(x))
+            .copied()
+            .collect::<Vec<_>>();
+        let removed = self
+            .fulfillment_authorities
+            .iter()
+            .filter(|x| !new.fulfillment_authorities.contains(x))
+            .copied()
+            .collect::<Vec<_>>();
+        if !added.is_empty() || !removed.is_empty() {
+            changes.push(StatusChange::FulfillmentAuthoritiesChanged { added, removed });
+        }
+
+        let old_asset = self.additional_asset.map(|(asset, _)| asset);
+        let new_asset = new.additional_asset.map(|(asset, _)| asset);
+        if old_asset != new_asset {
+            changes.push(StatusChange::AdditionalAssetChanged {
+                old: old_asset,
+                new: new_asset,
+            });
+        }
+
+        // fees of assets configured in both snapshots
+        for asset in std::iter::once(base_asset_id).chain(new_asset) {
+            if let (Some(old), Some(new)) = (
+                self.asset(asset, base_asset_id),
+                new.asset(asset, base_asset_id),
+            ) {
+                if old.fee != new.fee {
+                    changes.push(StatusChange::FeeChanged {
+                        asset,
+                        old: old.fee,
+                        new: new.fee,
+                    });
+                }
+            }
+        }
+
+        for (asset, threshold) in balance_thresholds.iter().copied() {
+            let old = self
+                .asset(asset, base_asset_id)
+                .map_or(0, |status| status.balance);
+            let balance = new
+                .asset(asset, base_asset_id)
+                .map_or(0, |status| status.balance);
+            if (old >= threshold) != (balance >= threshold) {
+                changes.push(StatusChange::BalanceCrossedThreshold {
+                    asset,
+                    threshold,
+                    balance,
+                });
+            }
+        }
+
+        if new.num_requests > self.num_requests {
+            changes.push(StatusChange::RequestsAdvanced {
+                old: self.num_requests,
+                new: new.num_requests,
+            });
+        }
+
+        changes
+    }
+}
+
+/// Status monitor.
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # use fuels::prelude::*;
+/// # use futures::StreamExt;
+/// # tokio_test::block_on(async {
+/// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+/// # let base_asset_id: AssetId = panic!();
+/// let changes = instance
+///     .watch_status(Duration::from_secs(10))
+///     .balance_threshold(base_asset_id, 1_000_000)
+///     .stream();
+/// futures::pin_mut!(changes);
+/// while let Some(change) = changes.next().await.transpose()? {
+///     println!("{change}");
+/// }
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug)]
+pub struct StatusWatch<'a, A: Account> {
+    instance: &'a Vrf<A>,
+    interval: Duration,
+    balance_thresholds: Vec<(AssetId, u64)>,
+}
+
+impl<'a, A: Account> StatusWatch<'a, A> {
+    /// Report the balance of the given asset crossing the threshold.
+    pub fn balance_threshold(mut self, asset: AssetId, threshold: u64) -> Self {
+        self.balance_thresholds.push((asset, threshold));
+        self
+    }
+
+    /// Returns a stream of status changes.
+    ///
+    /// The first snapshot is taken immediately and is only used as the baseline.
+    /// The stream ends on the first error.
+    pub fn stream(self) -> impl Stream<Item = Result<StatusChange>> + 'a {
+        stream::try_unfold(
+            (self, None::<(AssetId, Status)>, VecDeque::new()),
+            |(watch, mut previous, mut pending)| async move {
+                loop {
+                    if let Some(change) = pending.pop_front() {
+                        return Ok(Some((change, (watch, previous, pending))));
+                    }
+
+                    let base_asset_id = match &previous {
+                        Some((base_asset_id, _)) => {
+                            sleep(watch.interval).await;
+                            *base_asset_id
+                        }
+                        None => watch.instance.base_asset_id().await?,
+                    };
+                    let status = watch.instance.get_status().await?;
+                    if let Some((_, previous)) = &previous {
+                        pending = previous
+                            .diff(&status, base_asset_id, &watch.balance_thresholds)
+                            .into();
+                    }
+                    previous = Some((base_asset_id, status));
+                }
+            },
+        )
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Returns a monitor polling [`Vrf::get_status`] with the given interval.
+    pub fn watch_status(&self, interval: Duration) -> StatusWatch<'_, A> {
+        StatusWatch {
+            instance: self,
+            interval,
+            balance_thresholds: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AssetStatus;
+
+    const BASE: AssetId = AssetId::new([0; 32]);
+    const ASSET: AssetId = AssetId::new([1; 32]);
+
+    fn status() -> Status {
+        Status {
+            authority: Some(Identity::Address(Address::new([1; 32]))),
+            num_requests: 10,
+            base_asset: AssetStatus {
+                fee: 100,
+                balance: 1_000,
+            },
+            fulfillment_authorities: vec![Address::new([2; 32]), Address::new([3; 32])],
+            additional_asset: Some((
+                ASSET,
+                AssetStatus {
+                    fee: 5,
+                    balance: 50,
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn unchanged() {
+        assert_eq!(status().diff(&status(), BASE, &[(BASE, 500)]), vec![]);
+    }
+
+    #[test]
+    fn authority() {
+        let mut new = status();
+        new.authority = None;
+        assert_eq!(
+            status().diff(&new, BASE, &[]),
+            vec![StatusChange::AuthorityChanged {
+                old: status().authority,
+                new: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn fulfillment_authorities() {
+        let mut new = status();
+        new.fulfillment_authorities = vec![Address::new([3; 32]), Address::new([4; 32])];
+        assert_eq!(
+            status().diff(&new, BASE, &[]),
+            vec![StatusChange::FulfillmentAuthoritiesChanged {
+                added: vec![Address::new([4; 32])],
+                removed: vec![Address::new([2; 32])],
+            }]
+        );
+
+        // order is not a change
+        new.fulfillment_authorities = vec![Address::new([3; 32]), Address::new([2; 32])];
+        assert_eq!(status().diff(&new, BASE, &[]), vec![]);
+    }
+
+    #[test]
+    fn additional_asset() {
+        let mut unset = status();
+        unset.additional_asset = None;
+        assert_eq!(
+            status().diff(&unset, BASE, &[]),
+            vec![StatusChange::AdditionalAssetChanged {
+                old: Some(ASSET),
+                new: None,
+            }]
+        );
+        assert_eq!(
+            unset.diff(&status(), BASE, &[]),
+            vec![StatusChange::AdditionalAssetChanged {
+                old: None,
+                new: Some(ASSET),
+            }]
+        );
+
+        // fee of the replacing asset is not compared with the fee of the replaced one
+        let mut replaced = status();
+        replaced.additional_asset =
+            Some((AssetId::new([2; 32]), AssetStatus { fee: 7, balance: 0 }));
+        assert_eq!(
+            status().diff(&replaced, BASE, &[]),
+            vec![StatusChange::AdditionalAssetChanged {
+                old: Some(ASSET),
+                new: Some(AssetId::new([2; 32])),
+            }]
+        );
+
+        // a disabled asset is still configured
+        let mut disabled = status();
+        disabled.additional_asset.as_mut().unwrap().1.fee = 0;
+        assert_eq!(
+            status().diff(&disabled, BASE, &[]),
+            vec![StatusChange::FeeChanged {
+                asset: ASSET,
+                old: 5,
+                new: 0,
+            }]
+        );
+    }
+
+    #[test]
+    fn fees() {
+        let mut new = status();
+        new.base_asset.fee = 200;
+        new.additional_asset.as_mut().unwrap().1.fee = 6;
+        assert_eq!(
+            status().diff(&new, BASE, &[]),
+            vec![
+                StatusChange::FeeChanged {
+                    asset: BASE,
+                    old: 100,
+                    new: 200,
+                },
+                StatusChange::FeeChanged {
+                    asset: ASSET,
+                    old: 5,
+                    new: 6,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn balance_thresholds() {
+        let thresholds = [(BASE, 500), (ASSET, 50)];
+
+        let mut below = status();
+        below.base_asset.balance = 499;
+        below.additional_asset.as_mut().unwrap().1.balance = 60;
+        assert_eq!(
+            status().diff(&below, BASE, &thresholds),
+            vec![StatusChange::BalanceCrossedThreshold {
+                asset: BASE,
+                threshold: 500,
+                balance: 499,
+            }]
+        );
+        assert_eq!(
+            below.diff(&status(), BASE, &thresholds),
+            vec![StatusChange::BalanceCrossedThreshold {
+                asset: BASE,
+                threshold: 500,
+                balance: 1_000,
+            }]
+        );
+
+        // balance of an unset asset is zero
+        let mut unset = status();
+        unset.additional_asset = None;
+        assert_eq!(
+            status().diff(&unset, BASE, &thresholds),
+            vec![
+                StatusChange::AdditionalAssetChanged {
+                    old: Some(ASSET),
+                    new: None,
+                },
+                StatusChange::BalanceCrossedThreshold {
+                    asset: ASSET,
+                    threshold: 50,
+                    balance: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn requests() {
+        let mut new = status();
+        new.num_requests = 12;
+        assert_eq!(
+            status().diff(&new, BASE, &[]),
+            vec![StatusChange::RequestsAdvanced { old: 10, new: 12 }]
+        );
+        assert_eq!(new.diff(&status(), BASE, &[]), vec![]);
+    }
+}
@@ -0,0 +1,118 @@
+//! Network presets.
+//!
+//! ```no_run
+//! # use fuels::prelude::*;
+//! # use orao_fuel_vrf::{network::Network, Vrf};
+//! # tokio_test::block_on(async {
+//! # let secret_key: fuels::crypto::SecretKey = panic!();
+//! let network = Network::Testnet;
+//! let provider = network.connect().await?;
+//! let wallet = WalletUnlocked::new_from_private_key(secret_key, Some(provider));
+//! let instance = Vrf::connect(network, wallet).await?;
+//! # orao_fuel_vrf::Result::Ok(()) });
+//! ```
+
+use std::time::Duration;
+
+use fuels::prelude::*;
+
+use crate::{Error, Result, Vrf, MAINNET_CONTRACT_ID, TESTNET_CONTRACT_ID};
+
+pub const MAINNET_ENDPOINT: &str = "https://mainnet.fuel.network/graphql";
+pub const TESTNET_ENDPOINT: &str = "https://testnet.fuel.network/graphql";
+pub const LOCAL_ENDPOINT: &str = "http://127.0.0.1:4000";
+
+pub const MAINNET_CHAIN_ID: u64 = 9889;
+pub const TESTNET_CHAIN_ID: u64 = 0;
+
+/// Timeout of [`Network::connect`].
+pub const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Fuel network with the VRF deployed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    /// Local node with the VRF deployed at the given id.
+    ///
+    /// Chain id is not checked.
+    Local {
+        contract_id: ContractId,
+    },
+    Custom {
+        endpoint: String,
+        /// VRF proxy id.
+        contract_id: ContractId,
+        /// Expected chain id (not checked if `None`).
+        chain_id: Option<u64>,
+    },
+}
+
+impl Network {
+    /// GraphQL endpoint of the network.
+    pub fn endpoint(&self) -> &str {
+        match self {
+            Network::Mainnet => MAINNET_ENDPOINT,
+            Network::Testnet => TESTNET_ENDPOINT,
+            Network::Local { .. } => LOCAL_ENDPOINT,
+            Network::Custom { endpoint, .. } => endpoint,
+        }
+    }
+
+    /// VRF proxy id on this network.
+    pub fn contract_id(&self) -> ContractId {
+        match self {
+            Network::Mainnet => MAINNET_CONTRACT_ID,
+            Network::Testnet => TESTNET_CONTRACT_ID,
+            Network::Local { contract_id } | Network::Custom { contract_id, .. } => *contract_id,
+        }
+    }
+
+    /// Expected chain id (`None` means that it is not checked).
+    pub fn chain_id(&self) -> Option<u64> {
+        match self {
+            Network::Mainnet => Some(MAINNET_CHAIN_ID),
+            Network::Testnet => Some(TESTNET_CHAIN_ID),
+            Network::Local { .. } => None,
+            Network::Custom { chain_id, .. } => *chain_id,
+        }
+    }
+
+    /// Connects to the network endpoint and checks the chain id.
+    pub async fn connect(&self) -> Result<Provider> {
+        let provider = tokio::time::timeout(CONNECT_TIMEOUT, Provider::connect(self.endpoint()))
+            .await
+            .map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    format!("unable to connect to {}", self.endpoint()),
+                )
+            })??;
+        self.check_chain_id(&provider).await?;
+        Ok(provider)
+    }
+
+    /// Makes sure that the given provider is connected to this network.
+    ///
+    /// Returns [`Error::ChainIdMismatch`] otherwise.
+    pub async fn check_chain_id(&self, provider: &Provider) -> Result<()> {
+        let Some(expected) = self.chain_id() else {
+            return Ok(());
+        };
+        let actual = *provider.consensus_parameters().await?.chain_id();
+        if actual != expected {
+            return Err(Error::ChainIdMismatch { expected, actual });
+        }
+        Ok(())
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Creates an instance of the VRF deployed on the given network.
+    ///
+    /// Makes sure that the wallet's provider is connected to this network.
+    pub async fn connect(network: Network, wallet: A) -> Result<Self> {
+        network.check_chain_id(wallet.try_provider()?).await?;
+        Self::try_new(network.contract_id(), wallet).await
+    }
+}
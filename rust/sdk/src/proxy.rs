@@ -0,0 +1,207 @@
+//! VRF proxy management.
+//!
+//! VRF contract is deployed behind an upgradable proxy: clients call the proxy,
+//! which delegates to the current target (implementation) contract.
+
+use fuels::{
+    prelude::*,
+    programs::responses::CallResponse,
+    types::{errors::transaction::Reason, Identity},
+};
+
+use crate::{abi, AccessError, Error, Result, State};
+
+/// Handle of a VRF proxy contract.
+///
+/// ```no_run
+/// # use fuels::prelude::*;
+/// # use orao_fuel_vrf::proxy::Proxy;
+/// # tokio_test::block_on(async {
+/// # let wallet: WalletUnlocked = panic!();
+/// # let new_target: ContractId = panic!();
+/// let proxy = Proxy::new(orao_fuel_vrf::TESTNET_CONTRACT_ID, wallet);
+/// println!("Current target: {:?}", proxy.target().await?);
+/// proxy.upgrade_to(new_target).await?;
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug)]
+pub struct Proxy<A: Account> {
+    pub abi: abi::bindings::Proxy<A>,
+    pub contract_id: ContractId,
+}
+
+impl<A: Account> Proxy<A> {
+    pub fn new(contract_id: ContractId, account: A) -> Self {
+        Self {
+            abi: abi::bindings::Proxy::new(contract_id, account),
+            contract_id,
+        }
+    }
+
+    /// Converts the given error into a typed error if it is a proxy revert.
+    pub fn decode_error(&self, error: fuels::types::errors::Error) -> Error {
+        match error {
+            fuels::types::errors::Error::Transaction(
+                fuels::types::errors::transaction::Reason::Reverted { ref receipts, .. },
+            ) => match crate::last_log::<AccessError>(&self.abi.log_decoder(), receipts) {
+                Some(x) => Error::Access(x),
+                None => error.into(),
+            },
+            _ => error.into(),
+        }
+    }
+
+    /// Returns the current target contract.
+    pub async fn target(&self) -> Result<Option<ContractId>> {
+        Ok(self
+            .abi
+            .methods()
+            .proxy_target()
+            .simulate(Execution::StateReadOnly)
+            .await
+            .map_err(|e| self.decode_error(e))?
+            .value)
+    }
+
+    /// Returns the proxy owner.
+    ///
+    /// `None` means that the owner is not initialized or the ownership is revoked.
+    pub async fn owner(&self) -> Result<Option<Identity>> {
+        match self
+            .abi
+            .methods()
+            .proxy_owner()
+            .simulate(Execution::StateReadOnly)
+            .await
+            .map_err(|e| self.decode_error(e))?
+            .value
+        {
+            State::Initialized(owner) => Ok(Some(owner)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Makes sure that the given contract implements the VRF ABI.
+    ///
+    /// Returns [`Error::NotVrf`] if the VRF query reverts.
+    pub async fn check_target(&self, target: ContractId) -> Result<()> {
+        match abi::bindings::Vrf::new(target, self.abi.account())
+            .methods()
+            .get_num_requests()
+            .simulate(Execution::StateReadOnly)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(fuels::types::errors::Error::Transaction(Reason::Reverted { .. })) => {
+                Err(Error::NotVrf(target))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Switches the proxy to the new target contract.
+    ///
+    /// The target is checked with [`Proxy::check_target`] beforehand.
+    /// Note that existing [`crate::Vrf`] instances keep the old target –
+    /// recreate them after the upgrade.
+    pub async fn upgrade_to(&self, new_target: ContractId) -> Result<CallResponse<()>> {
+        self.check_target(new_target).await?;
+        self.abi
+            .methods()
+            .set_proxy_target(new_target)
+            .call()
+            .await
+            .map_err(|e| self.decode_error(e))
+    }
+
+    /// Transfers the proxy ownership.
+    pub async fn transfer_ownership(&self, new_owner: Address) -> Result<CallResponse<()>> {
+        self.abi
+            .methods()
+            .set_proxy_owner(new_owner)
+            .call()
+            .await
+            .map_err(|e| self.decode_error(e))
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use fuel_asm::op;
+
+    use fuels::types::Bits256;
+
+    use super::*;
+    use crate::{
+        testing::{vrf_contract, TestConfig, TestEnv},
+        Vrf,
+    };
+
+    #[tokio::test]
+    async fn check_target() -> Result<()> {
+        let env = TestEnv::launch().await?;
+        let proxy = Proxy::new(env.vrf.contract_id, env.authority.clone());
+        let vrf = env.vrf.target_contract_id.unwrap_or(env.vrf.contract_id);
+        proxy.check_target(vrf).await?;
+
+        // reverts on any call, like a contract without the called function
+        let code = [op::movi(0x10, 123), op::rvrt(0x10)]
+            .into_iter()
+            .collect::<Vec<u8>>();
+        let contract_id = Contract::regular(code, Salt::zeroed(), vec![])
+            .deploy(&env.authority, TxPolicies::default())
+            .await?;
+        let contract_id = (&contract_id).into();
+        assert!(matches!(
+            proxy.check_target(contract_id).await,
+            Err(Error::NotVrf(x)) if x == contract_id
+        ));
+
+        // a missing contract fails the query instead of reverting it
+        assert!(matches!(
+            proxy.check_target(ContractId::new([1; 32])).await,
+            Err(Error::Fuels(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore = "requires the `forc build` output of the proxy at `ORAO_FUEL_VRF_PROXY_BINARY`"]
+    async fn through_proxy() -> Result<()> {
+        let config = TestConfig::from_env();
+        assert!(config.proxy_binary.is_some(), "proxy binary is not set");
+        let env = TestEnv::launch_with(config.clone()).await?;
+        let proxy = Proxy::new(env.vrf.contract_id, env.authority.clone());
+        let target = env.vrf.target_contract_id.expect("proxy target");
+        assert_eq!(proxy.target().await?, Some(target));
+        assert_eq!(
+            proxy.owner().await?,
+            Some(Identity::Address(env.authority.address().into()))
+        );
+        proxy.check_target(target).await?;
+
+        let seed = Bits256([1; 32]);
+        env.request(seed).await?;
+        env.fulfill(seed).await?;
+
+        let new_target = vrf_contract(config.vrf_binary.as_deref(), Salt::new([1; 32]))?
+            .deploy(&env.authority, TxPolicies::default())
+            .await?;
+        let new_target = (&new_target).into();
+        proxy.upgrade_to(new_target).await?;
+        assert_eq!(proxy.target().await?, Some(new_target));
+        let vrf = Vrf::try_new(proxy.contract_id, env.authority.clone()).await?;
+        assert_eq!(vrf.target_contract_id, Some(new_target));
+        // the storage belongs to the proxy, so it survives the upgrade
+        assert!(vrf.get_request_by_seed(seed).await?.is_some());
+
+        let new_owner = env.client.address().into();
+        proxy.transfer_ownership(new_owner).await?;
+        assert_eq!(proxy.owner().await?, Some(Identity::Address(new_owner)));
+        assert!(matches!(
+            proxy.upgrade_to(target).await,
+            Err(Error::Access(AccessError::NotOwner))
+        ));
+        Ok(())
+    }
+}
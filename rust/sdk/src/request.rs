@@ -0,0 +1,221 @@
+//! Randomness request builder.
+
+use std::sync::OnceLock;
+
+use fuels::{prelude::*, programs::calls::CallHandler, tx::TxId, types::Bits256};
+
+use crate::{Error, Event, Request, Result, Vrf, MULTI_CALL_GAS_LIMIT};
+
+/// Builds and sends a randomness request.
+///
+/// Picks the asset to pay the fee with and sets the call parameters accordingly.
+///
+/// ```no_run
+/// # use fuels::prelude::*;
+/// # use fuels::types::Bits256;
+/// # tokio_test::block_on(async {
+/// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+/// // Let's try to pay with additional asset with fallback to the base asset
+/// let receipt = instance
+///     .request_builder(Bits256([1_u8; 32]))
+///     .prefer_additional_asset()
+///     .send()
+///     .await?;
+/// println!("Request #{} paid {} of {}", receipt.num, receipt.fee, receipt.asset);
+/// # orao_fuel_vrf::Result::Ok(()) });
+/// ```
+#[derive(Debug)]
+pub struct RequestBuilder<'a, A: Account> {
+    instance: &'a Vrf<A>,
+    seed: Bits256,
+    prefer_additional_asset: bool,
+    callback_fee: u64,
+    tx_policies: TxPolicies,
+    /// Payment queried by [`RequestBuilder::payment`].
+    payment: OnceLock<(AssetId, u64)>,
+}
+
+/// Result of a successful randomness request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestReceipt {
+    /// Request number.
+    pub num: u64,
+    pub seed: Bits256,
+    /// Asset the fee was paid with.
+    pub asset: AssetId,
+    /// Fee paid (excluding the callback fee).
+    pub fee: u64,
+    pub callback_fee: u64,
+    pub tx_id: Option<TxId>,
+    /// `Request` event emitted by the contract.
+    pub event: Option<Request>,
+}
+
+impl<'a, A: Account> RequestBuilder<'a, A> {
+    pub(crate) fn new(instance: &'a Vrf<A>, seed: Bits256) -> Self {
+        Self {
+            instance,
+            seed,
+            prefer_additional_asset: false,
+            callback_fee: 0,
+            tx_policies: TxPolicies::default(),
+            payment: OnceLock::new(),
+        }
+    }
+
+    /// Pay with the additional asset if it is configured and enabled,
+    /// otherwise fall back to the base asset.
+    pub fn prefer_additional_asset(mut self) -> Self {
+        self.prefer_additional_asset = true;
+        self.payment = OnceLock::new();
+        self
+    }
+
+    /// Amount to pay on top of the fee to cover the consumer callback.
+    pub fn callback_fee(mut self, callback_fee: u64) -> Self {
+        self.callback_fee = callback_fee;
+        self
+    }
+
+    pub fn with_tx_policies(mut self, tx_policies: TxPolicies) -> Self {
+        self.tx_policies = tx_policies;
+        self
+    }
+
+    /// Returns the asset and the fee this request is going to be paid with.
+    ///
+    /// The fee configuration is queried once per builder: the base asset fee
+    /// along with the additional asset in a single multi-call, followed by
+    /// the additional asset fee lookup if it is preferred and configured.
+    pub async fn payment(&self) -> Result<(AssetId, u64)> {
+        if let Some(payment) = self.payment.get() {
+            return Ok(*payment);
+        }
+        let payment = self.query_payment().await?;
+        Ok(*self.payment.get_or_init(|| payment))
+    }
+
+    async fn query_payment(&self) -> Result<(AssetId, u64)> {
+        let instance = self.instance;
+        let base_asset_id = instance.base_asset_id().await?;
+        if !self.prefer_additional_asset {
+            return Ok((base_asset_id, instance.get_fee(base_asset_id).await?));
+        }
+
+        let mut call = CallHandler::new_multi_call(instance.abi.account())
+            .add_call(instance.with_target_contract(instance.methods.get_asset()))
+            .add_call(instance.with_target_contract(instance.methods.get_fee(base_asset_id)))
+            .with_tx_policies(TxPolicies::default().with_script_gas_limit(MULTI_CALL_GAS_LIMIT));
+        let (asset, base_fee) = call
+            .simulate::<(AssetId, u64)>(Execution::StateReadOnly)
+            .await
+            .map_err(|e| instance.decode_error(e))?
+            .value;
+        if asset != base_asset_id {
+            let fee = instance.get_fee(asset).await?;
+            if fee != 0 {
+                return Ok((asset, fee));
+            }
+        }
+        Ok((base_asset_id, base_fee))
+    }
+
+    /// Returns the asset and the total amount (fee and callback fee) this request
+    /// is going to be paid with.
+    ///
+    /// Use it to fund a consumer contract call that performs the request.
+    pub async fn amount(&self) -> Result<(AssetId, u64)> {
+        let (asset, fee) = self.payment().await?;
+        Ok((asset, self.total(fee)?))
+    }
+
+    fn total(&self, fee: u64) -> Result<u64> {
+        fee.checked_add(self.callback_fee)
+            .ok_or(Error::AmountOverflow {
+                fee,
+                callback_fee: self.callback_fee,
+            })
+    }
+
+    /// Sends the request.
+    pub async fn send(self) -> Result<RequestReceipt> {
+        let (asset, fee) = self.payment().await?;
+        let amount = self.total(fee)?;
+        let response = self
+            .instance
+            .request(self.seed)
+            .with_tx_policies(self.tx_policies)
+            .call_params(
+                CallParameters::default()
+                    .with_amount(amount)
+                    .with_asset_id(asset),
+            )?
+            .call()
+            .await
+            .map_err(|e| self.instance.decode_error(e))?;
+        // The request is already performed, so the event is optional.
+        let event = self
+            .instance
+            .decode_events(&response.receipts)
+            .unwrap_or_default()
+            .into_iter()
+            .find_map(|event| match event {
+                Event::Request(request) => Some(request),
+                _ => None,
+            });
+
+        Ok(RequestReceipt {
+            num: response.value,
+            seed: self.seed,
+            asset,
+            fee,
+            callback_fee: self.callback_fee,
+            tx_id: response.tx_id,
+            event,
+        })
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Returns a builder for the randomness request with the given seed.
+    pub fn request_builder(&self, seed: Bits256) -> RequestBuilder<'_, A> {
+        RequestBuilder::new(self, seed)
+    }
+
+    /// Requests randomness paying the callback fee on top of the base asset fee.
+    ///
+    /// See [`RequestBuilder::callback_fee`].
+    pub async fn request_with_callback(
+        &self,
+        seed: Bits256,
+        callback_fee: u64,
+    ) -> Result<RequestReceipt> {
+        self.request_builder(seed)
+            .callback_fee(callback_fee)
+            .send()
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "testing"))]
+mod tests {
+    use super::*;
+    use crate::testing::TestEnv;
+
+    #[tokio::test]
+    async fn callback_fee_overflow() -> Result<()> {
+        let env = TestEnv::launch().await?;
+        let vrf = env.client_vrf();
+        let builder = vrf.request_builder(Bits256([1; 32])).callback_fee(u64::MAX);
+        let overflow = |error: Error| {
+            matches!(
+                error,
+                Error::AmountOverflow { fee, callback_fee: u64::MAX } if fee == env.fee
+            )
+        };
+        assert!(overflow(builder.amount().await.unwrap_err()));
+        assert!(overflow(builder.send().await.unwrap_err()));
+        assert!(vrf.get_request_by_seed(Bits256([1; 32])).await?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+//! Request seed generation.
+//!
+//! A seed could be used only once – the request reverts with
+//! [`ContractError::SeedInUse`] otherwise. Use [`Vrf::fresh_seed`] to get a seed
+//! that is checked to be unused.
+
+use fuels::{crypto::Hasher, prelude::*, types::Bits256};
+
+use crate::{ContractError, Result, Vrf};
+
+/// Maximum number of seeds [`Vrf::fresh_seed_with`] tries before giving up.
+pub const MAX_ATTEMPTS: usize = 16;
+
+/// Returns a random seed.
+pub fn random() -> Bits256 {
+    Bits256(rand::random())
+}
+
+/// Derives a seed from an application-specific domain and a nonce.
+pub fn from_domain(domain: &[u8], nonce: u64) -> Bits256 {
+    Bits256(
+        *Hasher::default()
+            .chain(domain)
+            .chain(nonce.to_be_bytes())
+            .finalize(),
+    )
+}
+
+/// Derives a seed from an account address and a counter.
+pub fn from_account(account: &Address, counter: u64) -> Bits256 {
+    Bits256(
+        *Hasher::default()
+            .chain(account)
+            .chain(counter.to_be_bytes())
+            .finalize(),
+    )
+}
+
+/// Seed derivation strategy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedStrategy {
+    /// See [`random`].
+    Random,
+    /// See [`from_domain`].
+    Domain { domain: Vec<u8>, nonce: u64 },
+    /// See [`from_account`].
+    Account { account: Address, counter: u64 },
+}
+
+impl SeedStrategy {
+    /// Returns the next seed advancing the nonce or the counter.
+    pub fn next_seed(&mut self) -> Bits256 {
+        match self {
+            SeedStrategy::Random => random(),
+            SeedStrategy::Domain { domain, nonce } => {
+                let seed = from_domain(domain, *nonce);
+                *nonce += 1;
+                seed
+            }
+            SeedStrategy::Account { account, counter } => {
+                let seed = from_account(account, *counter);
+                *counter += 1;
+                seed
+            }
+        }
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Returns a random seed that is not yet used.
+    pub async fn fresh_seed(&self) -> Result<Bits256> {
+        self.fresh_seed_with(&mut SeedStrategy::Random).await
+    }
+
+    /// Returns the next seed of the given strategy that is not yet used.
+    ///
+    /// Gives up with [`ContractError::SeedInUse`] after [`MAX_ATTEMPTS`] used seeds.
+    pub async fn fresh_seed_with(&self, strategy: &mut SeedStrategy) -> Result<Bits256> {
+        for _ in 0..MAX_ATTEMPTS {
+            let seed = strategy.next_seed();
+            if self.get_request_by_seed(seed).await?.is_none() {
+                return Ok(seed);
+            }
+        }
+        Err(ContractError::SeedInUse.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Vectors are computed independently (Python `hashlib`).
+
+    #[test]
+    fn from_domain_vector() {
+        assert_eq!(
+            from_domain(b"orao", 7),
+            Bits256::from_hex_str(
+                "2d14af007ee3f84b941ddab9c606879c103425f694633dc2c43e594175a9b603"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn from_account_vector() {
+        assert_eq!(
+            from_account(&Address::new([0x11; 32]), 3),
+            Bits256::from_hex_str(
+                "fe7b9d1f13d230553021ca43eec5fbeda9916b0d4264c3477d0962f8e3ecc44b"
+            )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn next_seed() {
+        let mut strategy = SeedStrategy::Domain {
+            domain: b"orao".to_vec(),
+            nonce: 7,
+        };
+        assert_eq!(strategy.next_seed(), from_domain(b"orao", 7));
+        assert_eq!(strategy.next_seed(), from_domain(b"orao", 8));
+
+        let account = Address::new([0x11; 32]);
+        let mut strategy = SeedStrategy::Account {
+            account,
+            counter: 3,
+        };
+        assert_eq!(strategy.next_seed(), from_account(&account, 3));
+        assert_eq!(
+            strategy,
+            SeedStrategy::Account {
+                account,
+                counter: 4
+            }
+        );
+    }
+
+    #[cfg(feature = "testing")]
+    #[tokio::test]
+    async fn fresh_seed_with_skips_used() -> Result<()> {
+        let env = crate::testing::TestEnv::launch().await?;
+        let mut strategy = SeedStrategy::Domain {
+            domain: b"orao".to_vec(),
+            nonce: 0,
+        };
+        env.request(from_domain(b"orao", 0)).await?;
+        assert_eq!(
+            env.vrf.fresh_seed_with(&mut strategy).await?,
+            from_domain(b"orao", 1)
+        );
+
+        // gives up after MAX_ATTEMPTS used seeds
+        for nonce in 1..MAX_ATTEMPTS as u64 {
+            env.request(from_domain(b"orao", nonce)).await?;
+        }
+        let mut strategy = SeedStrategy::Domain {
+            domain: b"orao".to_vec(),
+            nonce: 0,
+        };
+        assert!(matches!(
+            env.vrf.fresh_seed_with(&mut strategy).await,
+            Err(crate::Error::Contract(ContractError::SeedInUse))
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+//! Serde support for the contract types (requires the `serde` feature).
+//!
+//! Seeds and randomness are hex-encoded, request states and events
+//! are tagged with the `type` field:
+//!
+//! ```json
+//! {
+//!   "sender": { "Address": "…" },
+//!   "seed": "…",
+//!   "callback_fee": 0,
+//!   "state": { "type": "fulfilled", "randomness": "…", "keys": ["…"] }
+//! }
+//! ```
+
+use fuels::types::{Address, Bits256, Bytes32, Bytes64, ContractId, Identity};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    abi::bindings::{Callback, FulfillersKeys},
+    bytes64_to_randomness, randomness_to_bytes64, Event, Fulfill, Fulfilled, Randomness,
+    RandomnessState, Request, Reset, Response, Unfulfilled,
+};
+
+#[derive(Serialize, Deserialize)]
+struct RandomnessRepr {
+    sender: Identity,
+    seed: Bytes32,
+    callback_fee: u64,
+    state: RandomnessStateRepr,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RandomnessStateRepr {
+    Unfulfilled {
+        randomness: Bytes64,
+        keys: Vec<Address>,
+    },
+    Fulfilled {
+        randomness: Bytes64,
+        keys: Vec<Address>,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum EventRepr {
+    Fulfill {
+        seed: Bytes32,
+        randomness: Bytes64,
+    },
+    Request {
+        seed: Bytes32,
+        client: Identity,
+        no: u64,
+    },
+    Response {
+        seed: Bytes32,
+        authority: Address,
+        randomness: Bytes64,
+    },
+    Reset {
+        seed: Bytes32,
+    },
+    Callback {
+        seed: Bytes32,
+        randomness: Bytes64,
+        client: ContractId,
+    },
+}
+
+fn seed(seed: Bits256) -> Bytes32 {
+    Bytes32::new(seed.0)
+}
+
+fn keys<E: de::Error>(keys: Vec<Address>) -> Result<FulfillersKeys, E> {
+    let mut result = FulfillersKeys {
+        keys: Default::default(),
+    };
+    if keys.len() > result.keys.len() {
+        return Err(E::invalid_length(
+            keys.len(),
+            &"no more keys than fulfillers",
+        ));
+    }
+    result.keys[..keys.len()].copy_from_slice(&keys);
+    Ok(result)
+}
+
+impl From<&RandomnessState> for RandomnessStateRepr {
+    fn from(state: &RandomnessState) -> Self {
+        match state {
+            RandomnessState::Unfulfilled(x) => RandomnessStateRepr::Unfulfilled {
+                randomness: randomness_to_bytes64(x.randomness),
+                keys: x.keys.iter().copied().collect(),
+            },
+            RandomnessState::Fulfilled(x) => RandomnessStateRepr::Fulfilled {
+                randomness: randomness_to_bytes64(x.randomness),
+                keys: x.keys.iter().copied().collect(),
+            },
+        }
+    }
+}
+
+impl RandomnessStateRepr {
+    fn into_state<E: de::Error>(self) -> Result<RandomnessState, E> {
+        Ok(match self {
+            RandomnessStateRepr::Unfulfilled { randomness, keys } => {
+                RandomnessState::Unfulfilled(Unfulfilled {
+                    randomness: bytes64_to_randomness(randomness),
+                    keys: self::keys(keys)?,
+                })
+            }
+            RandomnessStateRepr::Fulfilled { randomness, keys } => {
+                RandomnessState::Fulfilled(Fulfilled {
+                    randomness: bytes64_to_randomness(randomness),
+                    keys: self::keys(keys)?,
+                })
+            }
+        })
+    }
+}
+
+impl Serialize for RandomnessState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RandomnessStateRepr::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RandomnessState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        RandomnessStateRepr::deserialize(deserializer)?.into_state()
+    }
+}
+
+impl Serialize for Randomness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RandomnessRepr {
+            sender: self.sender,
+            seed: seed(self.seed),
+            callback_fee: self.callback_fee,
+            state: (&self.state).into(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Randomness {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RandomnessRepr::deserialize(deserializer)?;
+        Ok(Randomness {
+            sender: repr.sender,
+            seed: Bits256(*repr.seed),
+            callback_fee: repr.callback_fee,
+            state: repr.state.into_state()?,
+        })
+    }
+}
+
+impl Serialize for Event {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Event::Fulfill(x) => EventRepr::Fulfill {
+                seed: seed(x.seed),
+                randomness: randomness_to_bytes64(x.randomness),
+            },
+            Event::Request(x) => EventRepr::Request {
+                seed: seed(x.seed),
+                client: x.client,
+                no: x.no,
+            },
+            Event::Response(x) => EventRepr::Response {
+                seed: seed(x.seed),
+                authority: x.authority,
+                randomness: randomness_to_bytes64(x.randomness),
+            },
+            Event::Reset(x) => EventRepr::Reset { seed: seed(x.seed) },
+            Event::Callback(x) => EventRepr::Callback {
+                seed: seed(x.seed),
+                randomness: randomness_to_bytes64(x.randomness),
+                client: x.client,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Event {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match EventRepr::deserialize(deserializer)? {
+            EventRepr::Fulfill { seed, randomness } => Event::Fulfill(Fulfill {
+                seed: Bits256(*seed),
+                randomness: bytes64_to_randomness(randomness),
+            }),
+            EventRepr::Request { seed, client, no } => Event::Request(Request {
+                seed: Bits256(*seed),
+                client,
+                no,
+            }),
+            EventRepr::Response {
+                seed,
+                authority,
+                randomness,
+            } => Event::Response(Response {
+                seed: Bits256(*seed),
+                authority,
+                randomness: bytes64_to_randomness(randomness),
+            }),
+            EventRepr::Reset { seed } => Event::Reset(Reset {
+                seed: Bits256(*seed),
+            }),
+            EventRepr::Callback {
+                seed,
+                randomness,
+                client,
+            } => Event::Callback(Callback {
+                seed: Bits256(*seed),
+                randomness: bytes64_to_randomness(randomness),
+                client,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt;
+
+    use fuels::types::{AssetId, B512};
+    use serde::de::DeserializeOwned;
+
+    use super::*;
+    use crate::{AssetStatus, Status};
+
+    const SEED: Bits256 = Bits256([1; 32]);
+
+    fn randomness() -> B512 {
+        B512::from((Bits256([2; 32]), Bits256([3; 32])))
+    }
+
+    fn fulfillers_keys(count: u8) -> FulfillersKeys {
+        let mut keys = FulfillersKeys {
+            keys: Default::default(),
+        };
+        for (i, key) in keys.keys.iter_mut().take(count as usize).enumerate() {
+            *key = Address::new([i as u8 + 1; 32]);
+        }
+        keys
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + fmt::Debug>(value: T) {
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value, "{json}");
+    }
+
+    #[test]
+    fn randomness_round_trip() {
+        for state in [
+            RandomnessState::Unfulfilled(Unfulfilled {
+                randomness: randomness(),
+                keys: fulfillers_keys(2),
+            }),
+            RandomnessState::Fulfilled(Fulfilled {
+                randomness: randomness(),
+                keys: fulfillers_keys(7),
+            }),
+        ] {
+            round_trip(Randomness {
+                sender: Identity::ContractId(ContractId::new([4; 32])),
+                seed: SEED,
+                callback_fee: 5,
+                state,
+            });
+        }
+    }
+
+    #[test]
+    fn randomness_json() {
+        let value = serde_json::to_value(Randomness {
+            sender: Identity::Address(Address::new([4; 32])),
+            seed: SEED,
+            callback_fee: 0,
+            state: RandomnessState::Fulfilled(Fulfilled {
+                randomness: randomness(),
+                keys: fulfillers_keys(1),
+            }),
+        })
+        .unwrap();
+        assert_eq!(value["seed"], format!("{:x}", Bytes32::new(SEED.0)));
+        assert_eq!(value["state"]["type"], "fulfilled");
+        assert_eq!(
+            value["state"]["keys"],
+            serde_json::json!([format!("{:x}", Address::new([1; 32]))])
+        );
+    }
+
+    #[test]
+    fn too_many_keys() {
+        let mut value = serde_json::to_value(RandomnessState::Unfulfilled(Unfulfilled {
+            randomness: randomness(),
+            keys: fulfillers_keys(7),
+        }))
+        .unwrap();
+        value["keys"]
+            .as_array_mut()
+            .unwrap()
+            .push(serde_json::to_value(Address::new([8; 32])).unwrap());
+        let error = serde_json::from_value::<RandomnessState>(value).unwrap_err();
+        assert!(error.to_string().contains("invalid length 8"), "{error}");
+    }
+
+    #[test]
+    fn event_round_trip() {
+        for event in [
+            Event::Fulfill(Fulfill {
+                seed: SEED,
+                randomness: randomness(),
+            }),
+            Event::Request(Request {
+                seed: SEED,
+                client: Identity::Address(Address::new([4; 32])),
+                no: 6,
+            }),
+            Event::Response(Response {
+                seed: SEED,
+                authority: Address::new([5; 32]),
+                randomness: randomness(),
+            }),
+            Event::Reset(Reset { seed: SEED }),
+            Event::Callback(Callback {
+                seed: SEED,
+                randomness: randomness(),
+                client: ContractId::new([4; 32]),
+            }),
+        ] {
+            round_trip(event);
+        }
+    }
+
+    #[test]
+    fn status_round_trip() {
+        round_trip(Status {
+            authority: Some(Identity::Address(Address::new([1; 32]))),
+            num_requests: 10,
+            base_asset: AssetStatus {
+                fee: 100,
+                balance: 1_000,
+            },
+            fulfillment_authorities: vec![Address::new([2; 32])],
+            additional_asset: Some((
+                AssetId::new([3; 32]),
+                AssetStatus {
+                    fee: 5,
+                    balance: 50,
+                },
+            )),
+        });
+    }
+}
@@ -0,0 +1,579 @@
+//! Local test environment (requires the `testing` feature).
+//!
+//! Starts an in-process Fuel node, deploys the VRF contract (optionally behind
+//! the proxy) and a sample consumer, and configures the VRF with generated
+//! fulfillment authorities, so that consumers are able to drive requests to
+//! fulfillment offline. See [`crate::mock`] for tests that don't need real
+//! fulfillment authorities.
+//!
+//! The VRF contract binary is shipped with the SDK (`contracts/vrf.bin`).
+//! Point [`VRF_BINARY_ENV`] to another `forc build` output and [`PROXY_BINARY_ENV`]
+//! to the proxy binary to deploy the VRF behind the proxy (the storage slots
+//! files next to the binaries are loaded automatically) or use [`TestConfig::new`].
+//! The sample consumer is the Sway contract in `rust/sdk/consumer`, point
+//! [`CONSUMER_BINARY_ENV`] to its `forc build` output to deploy it, otherwise
+//! an assembled stand-in with the same ABI is deployed.
+//!
+//! ```
+//! # use fuels::types::Bits256;
+//! # use orao_fuel_vrf::testing::TestEnv;
+//! # tokio_test::block_on(async {
+//! let env = TestEnv::launch().await?;
+//! let seed = Bits256([1_u8; 32]);
+//! env.request(seed).await?;
+//! let fulfilled = env.fulfill(seed).await?;
+//! # orao_fuel_vrf::Result::Ok(()) });
+//! ```
+
+use std::{
+    collections::HashMap,
+    env, io,
+    path::{Path, PathBuf},
+};
+
+use fuels::{
+    core::codec::{encode_fn_selector, EncoderConfig, LogDecoder},
+    crypto::SecretKey,
+    prelude::*,
+    programs::{
+        calls::{CallHandler, ContractCall},
+        contract::Regular,
+        responses::CallResponse,
+    },
+    tx::StorageSlot,
+    types::{Bits256, Bytes32, Identity, Token},
+};
+
+use crate::{
+    fulfiller::Fulfiller, request::RequestReceipt, verify::quorum, ContractError, Fulfilled,
+    RandomnessState, Result, Vrf, MAX_AUTHORITIES,
+};
+
+/// Environment variable with the path to the VRF contract binary.
+pub const VRF_BINARY_ENV: &str = "ORAO_FUEL_VRF_BINARY";
+/// Environment variable with the path to the proxy contract binary.
+pub const PROXY_BINARY_ENV: &str = "ORAO_FUEL_VRF_PROXY_BINARY";
+/// Environment variable with the path to the sample consumer binary.
+pub const CONSUMER_BINARY_ENV: &str = "ORAO_FUEL_VRF_CONSUMER_BINARY";
+
+/// SRC-14 storage slot of the proxy target (`sha256("storage_SRC14_0")`).
+const PROXY_TARGET_SLOT: [u8; 32] = [
+    0x7b, 0xb4, 0x58, 0xad, 0xc1, 0xd1, 0x18, 0x71, 0x33, 0x19, 0xa5, 0xba, 0xa0, 0x0a, 0x2d, 0x04,
+    0x9d, 0xd6, 0x4d, 0x29, 0x16, 0x47, 0x7d, 0x26, 0x88, 0xd7, 0x69, 0x70, 0xc8, 0x98, 0xcd, 0x55,
+];
+/// SRC-14 storage slot of the proxy owner (`sha256("storage_SRC14_1")`).
+const PROXY_OWNER_SLOT: [u8; 32] = [
+    0xbb, 0x79, 0x92, 0x7b, 0x15, 0xd9, 0x25, 0x9e, 0xa3, 0x16, 0xf2, 0xec, 0xb2, 0x29, 0x7d, 0x6c,
+    0xc8, 0x85, 0x18, 0x88, 0xa9, 0x82, 0x78, 0xc0, 0xa2, 0xe0, 0x3e, 0x1a, 0x09, 0x1e, 0xa7, 0x54,
+];
+
+/// Bundled VRF contract binary.
+const VRF_BINARY: &[u8] = include_bytes!("../contracts/vrf.bin");
+/// Storage slots of the bundled VRF contract (`contracts/vrf-storage_slots.json`),
+/// all of them are initialized with zeroes.
+const VRF_STORAGE_SLOTS: [[u8; 32]; 3] = [
+    [
+        0x3f, 0x0d, 0x45, 0x9d, 0x45, 0x3d, 0x97, 0x07, 0x17, 0x73, 0x64, 0xb5, 0x82, 0xf1, 0x3c,
+        0x78, 0xd2, 0xea, 0xbd, 0xbc, 0xfe, 0xb8, 0xf4, 0x8c, 0x96, 0x39, 0xbb, 0xac, 0xfe, 0x8c,
+        0x65, 0xdf,
+    ],
+    [
+        0x3f, 0x0d, 0x45, 0x9d, 0x45, 0x3d, 0x97, 0x07, 0x17, 0x73, 0x64, 0xb5, 0x82, 0xf1, 0x3c,
+        0x78, 0xd2, 0xea, 0xbd, 0xbc, 0xfe, 0xb8, 0xf4, 0x8c, 0x96, 0x39, 0xbb, 0xac, 0xfe, 0x8c,
+        0x65, 0xe0,
+    ],
+    [
+        0x60, 0x0b, 0x8e, 0xd8, 0x9d, 0xc6, 0x4b, 0x75, 0x41, 0x3e, 0xc5, 0xed, 0x37, 0xeb, 0x34,
+        0xc3, 0x76, 0x3d, 0x8c, 0xa9, 0x66, 0xb3, 0x09, 0x3a, 0x90, 0xaf, 0x11, 0xff, 0x47, 0xd6,
+        0xa4, 0xdf,
+    ],
+];
+
+/// Test environment configuration.
+#[derive(Debug, Clone)]
+pub struct TestConfig {
+    /// VRF contract binary (the bundled one if `None`).
+    pub vrf_binary: Option<PathBuf>,
+    /// Proxy contract binary (the VRF is used directly if `None`).
+    pub proxy_binary: Option<PathBuf>,
+    /// Sample consumer binary (the assembled stand-in if `None`).
+    pub consumer_binary: Option<PathBuf>,
+    /// Number of generated fulfillment authorities.
+    pub num_authorities: usize,
+    /// Base asset fee.
+    pub fee: u64,
+}
+
+impl Default for TestConfig {
+    fn default() -> Self {
+        Self {
+            vrf_binary: None,
+            proxy_binary: None,
+            consumer_binary: None,
+            num_authorities: 3,
+            fee: 1_000,
+        }
+    }
+}
+
+impl TestConfig {
+    /// Deploys the given VRF binary behind the given proxy binary.
+    pub fn new(vrf_binary: impl Into<PathBuf>, proxy_binary: impl Into<PathBuf>) -> Self {
+        Self {
+            vrf_binary: Some(vrf_binary.into()),
+            proxy_binary: Some(proxy_binary.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Reads binary paths from [`VRF_BINARY_ENV`], [`PROXY_BINARY_ENV`] and
+    /// [`CONSUMER_BINARY_ENV`] (unset variables fall back to the defaults).
+    pub fn from_env() -> Self {
+        Self {
+            vrf_binary: env::var_os(VRF_BINARY_ENV).map(Into::into),
+            proxy_binary: env::var_os(PROXY_BINARY_ENV).map(Into::into),
+            consumer_binary: env::var_os(CONSUMER_BINARY_ENV).map(Into::into),
+            ..Self::default()
+        }
+    }
+}
+
+/// Local node with the configured VRF.
+#[derive(Debug)]
+pub struct TestEnv {
+    pub provider: Provider,
+    /// VRF authority and proxy owner.
+    pub authority: WalletUnlocked,
+    /// Wallet to perform requests with.
+    pub client: WalletUnlocked,
+    pub fulfillers: Vec<Fulfiller>,
+    /// VRF instance bound to the authority wallet.
+    pub vrf: Vrf<WalletUnlocked>,
+    /// Sample consumer contract, see [`TestEnv::consumer_request`].
+    pub consumer: ContractId,
+    pub fee: u64,
+}
+
+impl TestEnv {
+    /// Launches the environment configured with [`TestConfig::from_env`].
+    pub async fn launch() -> Result<Self> {
+        Self::launch_with(TestConfig::from_env()).await
+    }
+
+    pub async fn launch_with(config: TestConfig) -> Result<Self> {
+        if !(1..=MAX_AUTHORITIES).contains(&config.num_authorities) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "invalid number of authorities: {} (expected 1..={MAX_AUTHORITIES})",
+                    config.num_authorities
+                ),
+            )
+            .into());
+        }
+
+        let mut wallets = launch_custom_provider_and_get_wallets(
+            WalletsConfig::new(Some(2), Some(1), Some(1_000_000_000)),
+            None,
+            None,
+        )
+        .await?;
+        let client = wallets.pop().expect("two wallets");
+        let authority = wallets.pop().expect("two wallets");
+        let provider = authority.try_provider()?.clone();
+
+        let mut contract_id = vrf_contract(config.vrf_binary.as_deref(), Salt::zeroed())?
+            .deploy(&authority, TxPolicies::default())
+            .await?;
+        if let Some(binary) = &config.proxy_binary {
+            contract_id = Contract::load_from(
+                binary,
+                LoadConfiguration::default().with_storage_configuration(
+                    StorageConfiguration::default().add_slot_overrides(proxy_storage_slots(
+                        (&contract_id).into(),
+                        authority.address().into(),
+                    )),
+                ),
+            )?
+            .deploy(&authority, TxPolicies::default())
+            .await?;
+        }
+
+        let mut rng = rand::thread_rng();
+        let fulfillers = (0..config.num_authorities)
+            .map(|_| Fulfiller::new(SecretKey::random(&mut rng)))
+            .collect::<Vec<_>>();
+
+        let vrf = Vrf::try_new((&contract_id).into(), authority.clone()).await?;
+        vrf.configure(
+            Identity::Address(authority.address().into()),
+            config.fee,
+            fulfillers.iter().map(Fulfiller::address).collect(),
+        )
+        .await?;
+
+        let consumer = match &config.consumer_binary {
+            Some(binary) => {
+                Contract::load_from(binary, LoadConfiguration::default())?
+                    .deploy(&client, TxPolicies::default())
+                    .await?
+            }
+            None => {
+                Contract::regular(consumer_code(), Salt::zeroed(), vec![])
+                    .deploy(&client, TxPolicies::default())
+                    .await?
+            }
+        };
+
+        Ok(Self {
+            provider,
+            authority,
+            client,
+            fulfillers,
+            vrf,
+            consumer: (&consumer).into(),
+            fee: config.fee,
+        })
+    }
+
+    /// Deploys a contract (e.g. a VRF consumer under test) from the client wallet.
+    pub async fn deploy(
+        &self,
+        binary: impl AsRef<Path>,
+        configuration: LoadConfiguration,
+    ) -> Result<ContractId> {
+        let contract_id = Contract::load_from(binary, configuration)?
+            .deploy(&self.client, TxPolicies::default())
+            .await?;
+        Ok((&contract_id).into())
+    }
+
+    /// Returns VRF instance bound to the client wallet.
+    pub fn client_vrf(&self) -> Vrf<WalletUnlocked> {
+        self.vrf.with_account(self.client.clone())
+    }
+
+    /// Performs a request from the client wallet paying with the base asset.
+    pub async fn request(&self, seed: Bits256) -> Result<RequestReceipt> {
+        self.client_vrf().request_builder(seed).send().await
+    }
+
+    /// Performs a request through the sample consumer, the client wallet pays
+    /// the fee and the callback fee.
+    ///
+    /// The VRF only executes callbacks of requests with a non-zero
+    /// callback fee, see [`TestEnv::consumer_execute_callback`].
+    pub async fn consumer_request(
+        &self,
+        seed: Bits256,
+        callback_fee: u64,
+    ) -> Result<CallResponse<()>> {
+        self.consumer_call(&self.vrf, "request", seed)
+            .call_params(CallParameters::default().with_amount(self.fee + callback_fee))?
+            .call()
+            .await
+            .map_err(|e| self.vrf.decode_error(e))
+    }
+
+    /// Makes the sample consumer execute its callback of the fulfilled request
+    /// (the VRF doesn't execute callbacks on fulfillment and only the consumer
+    /// is authorized to, see [`Vrf::execute_callback`]).
+    ///
+    /// The callback logs the seed and the randomness and transfers a coin of the base
+    /// asset to the address equal to the seed (fund the consumer beforehand).
+    pub async fn consumer_execute_callback(&self, seed: Bits256) -> Result<CallResponse<()>> {
+        self.consumer_call(&self.vrf, "execute_callback", seed)
+            .with_variable_output_policy(VariableOutputPolicy::Exactly(1))
+            .call()
+            .await
+            .map_err(|e| self.vrf.decode_error(e))
+    }
+
+    /// Calls the sample consumer method that forwards to the given VRF.
+    pub(crate) fn consumer_call<A: Account>(
+        &self,
+        vrf: &Vrf<A>,
+        method: &str,
+        seed: Bits256,
+    ) -> CallHandler<WalletUnlocked, ContractCall, ()> {
+        CallHandler::new_contract_call(
+            self.consumer.into(),
+            self.client.clone(),
+            encode_fn_selector(method),
+            &[Token::B256(*vrf.contract_id), Token::B256(seed.0)],
+            LogDecoder::new(HashMap::new()),
+            true,
+            EncoderConfig::default(),
+        )
+        .with_contract_ids(&vrf.contract_ids())
+    }
+
+    /// Submits responses of the generated authorities until the request is fulfilled.
+    pub async fn fulfill(&self, seed: Bits256) -> Result<Fulfilled> {
+        for fulfiller in self.fulfillers.iter().take(quorum(self.fulfillers.len())) {
+            fulfiller.fulfill(&self.vrf, seed).await?;
+        }
+        match self.vrf.get_request_by_seed(seed).await? {
+            Some(randomness) => match randomness.state {
+                RandomnessState::Fulfilled(fulfilled) => Ok(fulfilled),
+                RandomnessState::Unfulfilled(_) => Err(ContractError::UnFulfilled.into()),
+            },
+            None => Err(ContractError::UnknownRequest.into()),
+        }
+    }
+}
+
+/// Loads the given VRF binary (the bundled one if `None`) with the given salt.
+pub(crate) fn vrf_contract(binary: Option<&Path>, salt: Salt) -> Result<Contract<Regular>> {
+    match binary {
+        Some(binary) => Ok(Contract::load_from(
+            binary,
+            LoadConfiguration::default().with_salt(salt),
+        )?),
+        None => Ok(Contract::regular(
+            VRF_BINARY.to_vec(),
+            salt,
+            VRF_STORAGE_SLOTS
+                .iter()
+                .map(|key| StorageSlot::new(Bytes32::new(*key), Bytes32::zeroed()))
+                .collect(),
+        )),
+    }
+}
+
+/// Returns proxy storage slots that set the target and the owner.
+///
+/// Values are laid out as Sway stores them: enum tag word followed by the
+/// variant data, spanning consecutive slots.
+fn proxy_storage_slots(target: ContractId, owner: Address) -> Vec<StorageSlot> {
+    // Option::Some(target)
+    let mut target_value = 1_u64.to_be_bytes().to_vec();
+    target_value.extend_from_slice(&*target);
+    // State::Initialized(Identity::Address(owner))
+    let mut owner_value = 1_u64.to_be_bytes().to_vec();
+    owner_value.extend_from_slice(&0_u64.to_be_bytes());
+    owner_value.extend_from_slice(&*owner);
+
+    let mut slots = storage_slots(PROXY_TARGET_SLOT, &target_value);
+    slots.extend(storage_slots(PROXY_OWNER_SLOT, &owner_value));
+    slots
+}
+
+fn storage_slots(mut key: [u8; 32], value: &[u8]) -> Vec<StorageSlot> {
+    value
+        .chunks(32)
+        .map(|chunk| {
+            let mut slot_value = [0_u8; 32];
+            slot_value[..chunk.len()].copy_from_slice(chunk);
+            let slot = StorageSlot::new(Bytes32::new(key), Bytes32::new(slot_value));
+            // next slot key is the big-endian increment of the current one
+            for byte in key.iter_mut().rev() {
+                let (x, overflow) = byte.overflowing_add(1);
+                *byte = x;
+                if !overflow {
+                    break;
+                }
+            }
+            slot
+        })
+        .collect()
+}
+
+/// Assembles the stand-in for the sample consumer (`rust/sdk/consumer`),
+/// deployed unless [`TestConfig::consumer_binary`] is set.
+///
+/// -   `request(vrf: ContractId, seed: b256)` and `execute_callback(vrf: ContractId, seed: b256)`
+///     call the VRF method of the same name with the seed, forwarding the coins
+///     they are called with,
+/// -   `fulfill_randomness(seed: b256, randomness: B512)` logs `seed ++ randomness`
+///     and transfers a coin of the base asset to the address equal to the seed.
+///
+/// Methods are told apart by the selector length and, unlike the Sway consumer,
+/// the callback caller isn't checked.
+fn consumer_code() -> Vec<u8> {
+    use fuel_asm::{op, GMArgs, GTFArgs, RegId};
+
+    // call frame words with pointers to the selector and to the arguments
+    const SELECTOR: u16 = 584 / 8;
+    const ARGS: u16 = 592 / 8;
+    let (selector, args, tmp, count, index, variable) = (0x10, 0x11, 0x12, 0x13, 0x14, 0x15);
+
+    [
+        op::lw(selector, RegId::FP, SELECTOR),
+        op::lw(args, RegId::FP, ARGS),
+        op::lw(tmp, selector, 0),
+        op::movi(count, "fulfill_randomness".len() as u32),
+        op::eq(tmp, tmp, count),
+        op::jnzf(tmp, RegId::ZERO, 9),
+        // call {vrf, selector, seed}
+        op::movi(tmp, 48),
+        op::aloc(tmp),
+        op::mcpi(RegId::HP, args, 32),
+        op::sw(RegId::HP, selector, 4),
+        op::addi(tmp, args, 32),
+        op::sw(RegId::HP, tmp, 5),
+        op::addi(tmp, RegId::FP, 32),
+        op::call(RegId::HP, RegId::BAL, tmp, RegId::CGAS),
+        op::retd(RegId::ZERO, RegId::ZERO),
+        // fulfill_randomness
+        op::movi(tmp, 96),
+        op::logd(RegId::ZERO, RegId::ZERO, args, tmp),
+        // find the variable output
+        op::gtf_args(count, RegId::ZERO, GTFArgs::ScriptOutputsCount),
+        op::move_(index, RegId::ZERO),
+        op::movi(variable, 3),
+        op::gtf_args(tmp, index, GTFArgs::OutputType),
+        op::eq(tmp, tmp, variable),
+        op::jnzf(tmp, RegId::ZERO, 4),
+        op::addi(index, index, 1),
+        op::lt(tmp, index, count),
+        op::jnzb(tmp, RegId::ZERO, 4),
+        op::rvrt(RegId::ONE),
+        op::gm_args(tmp, GMArgs::BaseAssetId),
+        op::tro(args, index, RegId::ONE, tmp),
+        op::retd(RegId::ZERO, RegId::ZERO),
+    ]
+    .into_iter()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify::verify_fulfilled, Error, Response};
+
+    #[tokio::test]
+    async fn request_fulfill() -> Result<()> {
+        let env = TestEnv::launch_with(TestConfig::default()).await?;
+        let seed = Bits256([1; 32]);
+        env.request(seed).await?;
+        let fulfilled = env.fulfill(seed).await?;
+
+        let request = env.vrf.get_request_by_seed(seed).await?.expect("request");
+        assert!(matches!(request.state, RandomnessState::Fulfilled(_)));
+        let responses = env
+            .fulfillers
+            .iter()
+            .take(quorum(env.fulfillers.len()))
+            .map(|fulfiller| Response {
+                seed,
+                authority: fulfiller.address(),
+                randomness: fulfiller.sign(seed),
+            })
+            .collect::<Vec<_>>();
+        let authorities = env.vrf.get_fulfillment_authorities().await?;
+        assert!(verify_fulfilled(seed, &fulfilled, &responses, &authorities).is_valid());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn consumer_callback() -> Result<()> {
+        let env = TestEnv::launch_with(TestConfig::default()).await?;
+        let base_asset_id = *env.provider.consensus_parameters().await?.base_asset_id();
+        env.client
+            .force_transfer_to_contract(
+                &env.consumer.into(),
+                1,
+                base_asset_id,
+                TxPolicies::default(),
+            )
+            .await?;
+
+        let seed = Bits256([2; 32]);
+        env.consumer_request(seed, 1).await?;
+        let request = env.vrf.get_request_by_seed(seed).await?.expect("request");
+        assert_eq!(request.sender, Identity::ContractId(env.consumer));
+        assert_eq!(request.callback_fee, 1);
+
+        let balances = || async {
+            let consumer = env
+                .provider
+                .get_contract_asset_balance(&env.consumer.into(), base_asset_id)
+                .await?;
+            let seed_address = env
+                .provider
+                .get_asset_balance(&Address::new(seed.0).into(), base_asset_id)
+                .await?;
+            Result::Ok((consumer, seed_address))
+        };
+
+        // the fulfillment doesn't execute the callback
+        env.fulfill(seed).await?;
+        assert_eq!(balances().await?, (1, 0));
+
+        env.consumer_execute_callback(seed).await?;
+        assert_eq!(balances().await?, (0, 1));
+
+        // the callback is executed once
+        env.consumer_execute_callback(seed).await?;
+        assert_eq!(balances().await?, (0, 1));
+        Ok(())
+    }
+
+    #[test]
+    fn proxy_slots() {
+        use fuels::crypto::Hasher;
+
+        assert_eq!(*Hasher::hash(b"storage_SRC14_0"), PROXY_TARGET_SLOT);
+        assert_eq!(*Hasher::hash(b"storage_SRC14_1"), PROXY_OWNER_SLOT);
+
+        let next_key = |key: [u8; 32]| {
+            let mut key = key;
+            key[31] += 1;
+            Bytes32::new(key)
+        };
+        let target = ContractId::new([0xaa; 32]);
+        let owner = Address::new([0xbb; 32]);
+        let slots = proxy_storage_slots(target, owner);
+        let slots = slots
+            .iter()
+            .map(|slot| (*slot.key(), *slot.value()))
+            .collect::<Vec<_>>();
+
+        let mut expected = Vec::new();
+        // Option::Some tag word followed by the target
+        let mut value = [0_u8; 32];
+        value[7] = 1;
+        value[8..].copy_from_slice(&target[..24]);
+        expected.push((Bytes32::new(PROXY_TARGET_SLOT), Bytes32::new(value)));
+        let mut value = [0_u8; 32];
+        value[..8].copy_from_slice(&target[24..]);
+        expected.push((next_key(PROXY_TARGET_SLOT), Bytes32::new(value)));
+        // State::Initialized tag word, Identity::Address tag word and the owner
+        let mut value = [0_u8; 32];
+        value[7] = 1;
+        value[16..].copy_from_slice(&owner[..16]);
+        expected.push((Bytes32::new(PROXY_OWNER_SLOT), Bytes32::new(value)));
+        let mut value = [0_u8; 32];
+        value[..16].copy_from_slice(&owner[16..]);
+        expected.push((next_key(PROXY_OWNER_SLOT), Bytes32::new(value)));
+
+        assert_eq!(slots, expected);
+    }
+
+    #[test]
+    fn storage_slot_keys_carry() {
+        let mut key = [0_u8; 32];
+        key[30] = 0x01;
+        key[31] = 0xff;
+        let slots = storage_slots(key, &[0; 64]);
+        let mut next = [0_u8; 32];
+        next[30] = 0x02;
+        assert_eq!(*slots[0].key(), Bytes32::new(key));
+        assert_eq!(*slots[1].key(), Bytes32::new(next));
+    }
+
+    #[tokio::test]
+    async fn invalid_num_authorities() {
+        for num_authorities in [0, MAX_AUTHORITIES + 1] {
+            let config = TestConfig {
+                num_authorities,
+                ..TestConfig::default()
+            };
+            assert!(matches!(
+                TestEnv::launch_with(config).await,
+                Err(Error::Io(e)) if e.kind() == io::ErrorKind::InvalidInput
+            ));
+        }
+    }
+}
@@ -0,0 +1,334 @@
+//! Off-chain verification of fulfilled randomness.
+//!
+//! Fulfilled randomness is the XOR of the fulfillment authorities' signatures
+//! of the request seed (see `Unfulfilled::fulfill` in `abi/src/randomness.sw`).
+//! Given the individual [`Response`] events one is able to check that every
+//! response is a valid signature of a configured authority and that the
+//! resulting randomness is indeed their aggregate.
+
+use std::collections::HashSet;
+
+use fuels::{
+    crypto::Signature,
+    prelude::*,
+    types::{Bits256, B512},
+};
+
+use crate::{fulfiller, randomness_to_bytes64, Fulfilled, Response, Result, Vrf};
+
+/// Returns the number of responses required to fulfill a request
+/// given the number of configured fulfillment authorities.
+pub fn quorum(num_authorities: usize) -> usize {
+    num_authorities * 2 / 3 + 1
+}
+
+/// XORs two randomness values.
+pub fn xor(left: B512, right: B512) -> B512 {
+    let mut result = left;
+    for (dst, src) in result.bytes.iter_mut().zip(right.bytes.iter()) {
+        for (x, y) in dst.0.iter_mut().zip(src.0.iter()) {
+            *x ^= y;
+        }
+    }
+    result
+}
+
+/// Outcome of a single response verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseStatus {
+    /// Response is a valid signature of a configured authority.
+    Valid,
+    /// Response is given for another seed.
+    SeedMismatch,
+    /// Unable to recover the signer.
+    InvalidSignature,
+    /// Recovered signer differs from the response authority.
+    SignerMismatch,
+    /// Authority is not among configured fulfillment authorities.
+    UnknownAuthority,
+    /// Authority is already responded.
+    Duplicate,
+}
+
+/// Verification report of a single response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseVerification {
+    pub authority: Address,
+    /// Address recovered from the response signature.
+    pub signer: Option<Address>,
+    pub status: ResponseStatus,
+    /// Whether the authority is listed among the fulfilled keys,
+    /// i.e. whether this response is part of the resulting randomness.
+    pub included: bool,
+}
+
+/// Verification report of fulfilled randomness.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verification {
+    pub responses: Vec<ResponseVerification>,
+    /// Number of responses required to fulfill the request.
+    pub quorum: usize,
+    /// Number of valid responses that are part of the resulting randomness.
+    pub num_valid: usize,
+    /// Fulfilled keys that have no valid response.
+    pub missing: Vec<Address>,
+    /// Randomness recomputed from valid responses.
+    pub randomness: B512,
+    /// Whether the recomputed randomness equals the fulfilled one.
+    pub randomness_matches: bool,
+}
+
+impl Verification {
+    /// Returns `true` if the fulfilled randomness is proven by the responses.
+    pub fn is_valid(&self) -> bool {
+        self.randomness_matches && self.missing.is_empty() && self.num_valid >= self.quorum
+    }
+}
+
+/// Verifies fulfilled randomness against individual responses.
+///
+/// `authorities` are the configured fulfillment authorities
+/// (see [`Vrf::get_fulfillment_authorities`]).
+pub fn verify_fulfilled(
+    seed: Bits256,
+    fulfilled: &Fulfilled,
+    responses: &[Response],
+    authorities: &[Address],
+) -> Verification {
+    let keys = fulfilled.keys.iter().copied().collect::<HashSet<_>>();
+    let message = fulfiller::message(seed);
+
+    let mut seen = HashSet::new();
+    let mut randomness = B512::from((Bits256::zeroed(), Bits256::zeroed()));
+    let mut num_valid = 0;
+
+    let responses = responses
+        .iter()
+        .map(|response| {
+            let signer = Signature::from_bytes(*randomness_to_bytes64(response.randomness))
+                .recover(&message)
+                .ok()
+                .map(|public_key| Address::new(*public_key.hash()));
+            let included = keys.contains(&response.authority);
+
+            let status = if response.seed != seed {
+                ResponseStatus::SeedMismatch
+            } else if signer.is_none() {
+                ResponseStatus::InvalidSignature
+            } else if signer != Some(response.authority) {
+                ResponseStatus::SignerMismatch
+            } else if !authorities.contains(&response.authority) {
+                ResponseStatus::UnknownAuthority
+            } else if !seen.insert(response.authority) {
+                ResponseStatus::Duplicate
+            } else {
+                ResponseStatus::Valid
+            };
+
+            if status == ResponseStatus::Valid && included {
+                randomness = xor(randomness, response.randomness);
+                num_valid += 1;
+            }
+
+            ResponseVerification {
+                authority: response.authority,
+                signer,
+                status,
+                included,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let missing = fulfilled
+        .keys
+        .iter()
+        .filter(|key| {
+            !responses
+                .iter()
+                .any(|x| x.authority == **key && x.included && x.status == ResponseStatus::Valid)
+        })
+        .copied()
+        .collect();
+
+    Verification {
+        responses,
+        quorum: quorum(authorities.len()),
+        num_valid,
+        missing,
+        randomness_matches: randomness == fulfilled.randomness,
+        randomness,
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Verifies fulfilled randomness against configured fulfillment authorities.
+    ///
+    /// See [`verify_fulfilled`].
+    pub async fn verify_fulfilled(
+        &self,
+        seed: Bits256,
+        fulfilled: &Fulfilled,
+        responses: &[Response],
+    ) -> Result<Verification> {
+        let authorities = self.get_fulfillment_authorities().await?;
+        Ok(verify_fulfilled(seed, fulfilled, responses, &authorities))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fuels::crypto::SecretKey;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::abi::bindings::FulfillersKeys;
+
+    const SEED: Bits256 = Bits256([1; 32]);
+
+    fn secret_keys(count: u64) -> Vec<SecretKey> {
+        (0..count)
+            .map(|i| SecretKey::random(&mut StdRng::seed_from_u64(i)))
+            .collect()
+    }
+
+    fn response(secret_key: &SecretKey, seed: Bits256) -> Response {
+        Response {
+            seed,
+            authority: fulfiller::authority_address(secret_key),
+            randomness: fulfiller::sign(secret_key, seed),
+        }
+    }
+
+    fn fulfilled(responses: &[Response]) -> Fulfilled {
+        let mut keys = FulfillersKeys {
+            keys: Default::default(),
+        };
+        let mut randomness = B512::from((Bits256::zeroed(), Bits256::zeroed()));
+        for (key, response) in keys.keys.iter_mut().zip(responses) {
+            *key = response.authority;
+            randomness = xor(randomness, response.randomness);
+        }
+        Fulfilled { randomness, keys }
+    }
+
+    fn authorities(secret_keys: &[SecretKey]) -> Vec<Address> {
+        secret_keys
+            .iter()
+            .map(fulfiller::authority_address)
+            .collect()
+    }
+
+    fn statuses(verification: &Verification) -> Vec<ResponseStatus> {
+        verification.responses.iter().map(|x| x.status).collect()
+    }
+
+    #[test]
+    fn valid_quorum() {
+        let keys = secret_keys(4);
+        let responses = keys[..3]
+            .iter()
+            .map(|key| response(key, SEED))
+            .collect::<Vec<_>>();
+        let fulfilled = fulfilled(&responses);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys));
+        assert_eq!(verification.quorum, 3);
+        assert_eq!(verification.num_valid, 3);
+        assert_eq!(verification.randomness, fulfilled.randomness);
+        assert!(verification.missing.is_empty());
+        assert!(verification.is_valid());
+        assert!(verification
+            .responses
+            .iter()
+            .all(|x| x.status == ResponseStatus::Valid && x.included));
+    }
+
+    #[test]
+    fn wrong_seed() {
+        let keys = secret_keys(1);
+        let responses = [response(&keys[0], Bits256([2; 32]))];
+        let fulfilled = fulfilled(&responses);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys));
+        assert_eq!(statuses(&verification), [ResponseStatus::SeedMismatch]);
+        assert_eq!(verification.missing, authorities(&keys));
+        assert!(!verification.is_valid());
+    }
+
+    #[test]
+    fn signer_mismatch() {
+        let keys = secret_keys(2);
+        let mut forged = response(&keys[1], SEED);
+        forged.authority = fulfiller::authority_address(&keys[0]);
+        let responses = [forged];
+        let fulfilled = fulfilled(&responses);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys));
+        assert_eq!(statuses(&verification), [ResponseStatus::SignerMismatch]);
+        assert_eq!(
+            verification.responses[0].signer,
+            Some(fulfiller::authority_address(&keys[1]))
+        );
+        assert!(!verification.is_valid());
+    }
+
+    #[test]
+    fn unknown_authority() {
+        let keys = secret_keys(2);
+        let responses = [response(&keys[1], SEED)];
+        let fulfilled = fulfilled(&responses);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys[..1]));
+        assert_eq!(statuses(&verification), [ResponseStatus::UnknownAuthority]);
+        assert_eq!(verification.num_valid, 0);
+        assert!(!verification.is_valid());
+    }
+
+    #[test]
+    fn duplicate_after_reset() {
+        let keys = secret_keys(1);
+        // the authority responds again once the request is reset
+        let responses = [response(&keys[0], SEED), response(&keys[0], SEED)];
+        let fulfilled = fulfilled(&responses[..1]);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys));
+        assert_eq!(
+            statuses(&verification),
+            [ResponseStatus::Valid, ResponseStatus::Duplicate]
+        );
+        assert_eq!(verification.num_valid, 1);
+        assert!(verification.is_valid());
+    }
+
+    #[test]
+    fn missing_key() {
+        let keys = secret_keys(3);
+        let all = keys
+            .iter()
+            .map(|key| response(key, SEED))
+            .collect::<Vec<_>>();
+        let fulfilled = fulfilled(&all);
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &all[..2], &authorities(&keys));
+        assert_eq!(verification.missing, [all[2].authority]);
+        assert!(!verification.randomness_matches);
+        assert!(!verification.is_valid());
+    }
+
+    #[test]
+    fn xor_mismatch() {
+        let keys = secret_keys(1);
+        let responses = [response(&keys[0], SEED)];
+        let mut fulfilled = fulfilled(&responses);
+        fulfilled.randomness = xor(
+            fulfilled.randomness,
+            B512::from((Bits256([1; 32]), Bits256::zeroed())),
+        );
+
+        let verification = verify_fulfilled(SEED, &fulfilled, &responses, &authorities(&keys));
+        assert_eq!(statuses(&verification), [ResponseStatus::Valid]);
+        assert!(verification.missing.is_empty());
+        assert!(!verification.randomness_matches);
+        assert!(!verification.is_valid());
+    }
+}
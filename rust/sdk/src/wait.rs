@@ -0,0 +1,250 @@
+//! Waiting for randomness fulfillment.
+
+use std::time::Duration;
+
+use fuels::{prelude::*, types::Bits256};
+use tokio::time::{sleep, Instant};
+use tokio_util::sync::CancellationToken;
+
+use crate::{ContractError, Error, Fulfilled, RandomnessState, Result, Unfulfilled, Vrf};
+
+/// Identifies a randomness request either by its number or by its seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestKey {
+    Num(u64),
+    Seed(Bits256),
+}
+
+impl From<u64> for RequestKey {
+    fn from(num: u64) -> Self {
+        Self::Num(num)
+    }
+}
+
+impl From<Bits256> for RequestKey {
+    fn from(seed: Bits256) -> Self {
+        Self::Seed(seed)
+    }
+}
+
+/// Polling options for [`Vrf::wait_fulfilled`].
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    /// Initial polling interval.
+    pub interval: Duration,
+    /// Upper bound for the polling interval.
+    pub max_interval: Duration,
+    /// Multiplier applied to the polling interval after each unsuccessful attempt
+    /// (values below `1.0`, including NaN, are treated as `1.0`).
+    pub backoff: f64,
+    /// Gives up after this duration (waits forever if `None`).
+    pub timeout: Option<Duration>,
+    /// Gives up as soon as this token is cancelled.
+    pub cancellation: Option<CancellationToken>,
+    /// Returns [`Error::Reset`] once the request is observed to be reset.
+    pub fail_on_reset: bool,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(10),
+            backoff: 1.5,
+            timeout: None,
+            cancellation: None,
+            fail_on_reset: false,
+        }
+    }
+}
+
+impl WaitOptions {
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval;
+        self
+    }
+
+    /// Use `1.0` to poll with a fixed interval.
+    pub fn with_backoff(mut self, backoff: f64) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn with_cancellation(mut self, cancellation: CancellationToken) -> Self {
+        self.cancellation = Some(cancellation);
+        self
+    }
+
+    /// See [`WaitOptions::fail_on_reset`].
+    pub fn with_fail_on_reset(mut self) -> Self {
+        self.fail_on_reset = true;
+        self
+    }
+}
+
+impl<A: Account> Vrf<A> {
+    /// Waits for the given request to be fulfilled.
+    ///
+    /// Returns [`Error::Timeout`] with the last observed unfulfilled state
+    /// if the request is not fulfilled within the configured timeout,
+    /// or [`Error::Cancelled`] if the cancellation token is cancelled.
+    ///
+    /// An unknown seed fails with [`ContractError::UnknownRequest`] right away,
+    /// while an unknown request number is awaited, as it may be taken later.
+    ///
+    /// A reset request is re-fulfilled by authorities, so waiting continues through
+    /// resets unless [`WaitOptions::fail_on_reset`] is set. Resets are detected as
+    /// collected responses disappearing between polls, so a reset followed by
+    /// the same responses within a single polling interval goes unnoticed
+    /// (track [`Event::Reset`](crate::Event::Reset) events if that matters).
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use fuels::prelude::*;
+    /// # use orao_fuel_vrf::wait::WaitOptions;
+    /// # tokio_test::block_on(async {
+    /// # let instance: orao_fuel_vrf::Vrf<WalletUnlocked> = panic!();
+    /// # let num: u64 = panic!();
+    /// let fulfilled = instance
+    ///     .wait_fulfilled(num, WaitOptions::default().with_timeout(Duration::from_secs(60)))
+    ///     .await?;
+    /// # orao_fuel_vrf::Result::Ok(()) });
+    /// ```
+    pub async fn wait_fulfilled(
+        &self,
+        request: impl Into<RequestKey>,
+        options: WaitOptions,
+    ) -> Result<Fulfilled> {
+        let request = request.into();
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+        let mut interval = options.interval;
+        let mut last: Option<Box<Unfulfilled>> = None;
+
+        loop {
+            let randomness = match request {
+                RequestKey::Num(num) => self.get_request_by_num(num).await?,
+                RequestKey::Seed(seed) => self.get_request_by_seed(seed).await?,
+            };
+            match randomness.map(|x| x.state) {
+                Some(RandomnessState::Fulfilled(fulfilled)) => return Ok(fulfilled),
+                Some(RandomnessState::Unfulfilled(unfulfilled)) => {
+                    if let Some(previous) = last.take() {
+                        if options.fail_on_reset && is_reset(&previous, &unfulfilled) {
+                            return Err(Error::Reset { last: previous });
+                        }
+                    }
+                    last = Some(Box::new(unfulfilled))
+                }
+                None => {
+                    if let RequestKey::Seed(_) = request {
+                        return Err(ContractError::UnknownRequest.into());
+                    }
+                }
+            }
+
+            let mut delay = interval;
+            if let Some(deadline) = deadline {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(Error::Timeout { last });
+                }
+                delay = delay.min(deadline - now);
+            }
+
+            match options.cancellation {
+                Some(ref cancellation) => tokio::select! {
+                    _ = cancellation.cancelled() => return Err(Error::Cancelled),
+                    _ = sleep(delay) => (),
+                },
+                None => sleep(delay).await,
+            }
+
+            interval = next_interval(interval, &options);
+        }
+    }
+}
+
+/// Returns the polling interval following the given one.
+fn next_interval(interval: Duration, options: &WaitOptions) -> Duration {
+    // `max` maps NaN to 1.0 as well
+    let backoff = options.backoff.max(1.0);
+    Duration::try_from_secs_f64(interval.as_secs_f64() * backoff)
+        .map_or(options.max_interval, |x| x.min(options.max_interval))
+}
+
+/// Returns `true` if some of the previously collected responses are gone.
+fn is_reset(previous: &Unfulfilled, current: &Unfulfilled) -> bool {
+    previous
+        .keys
+        .iter()
+        .any(|key| !current.keys.iter().any(|x| x == key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn next(interval: u64, backoff: f64) -> Duration {
+        next_interval(
+            Duration::from_secs(interval),
+            &WaitOptions::default().with_backoff(backoff),
+        )
+    }
+
+    #[test]
+    fn backoff() {
+        assert_eq!(next(2, 1.5), Duration::from_secs(3));
+        assert_eq!(next(8, 1.5), Duration::from_secs(10));
+        assert_eq!(next(2, 1.0), Duration::from_secs(2));
+    }
+
+    #[test]
+    fn invalid_backoff() {
+        assert_eq!(next(2, 0.5), Duration::from_secs(2));
+        assert_eq!(next(2, -1.0), Duration::from_secs(2));
+        assert_eq!(next(2, f64::NAN), Duration::from_secs(2));
+        assert_eq!(next(2, f64::INFINITY), Duration::from_secs(10));
+    }
+
+    fn unfulfilled(keys: &[u8]) -> Unfulfilled {
+        let mut result = Unfulfilled {
+            randomness: fuels::types::B512::from((Bits256([2; 32]), Bits256([3; 32]))),
+            keys: crate::abi::bindings::FulfillersKeys {
+                keys: Default::default(),
+            },
+        };
+        for (dst, key) in result.keys.keys.iter_mut().zip(keys) {
+            *dst = Address::new([*key; 32]);
+        }
+        result
+    }
+
+    #[test]
+    fn reset() {
+        assert!(!is_reset(&unfulfilled(&[]), &unfulfilled(&[])));
+        assert!(!is_reset(&unfulfilled(&[]), &unfulfilled(&[1])));
+        assert!(!is_reset(&unfulfilled(&[1]), &unfulfilled(&[1, 2])));
+        // all the responses are dropped
+        assert!(is_reset(&unfulfilled(&[1, 2]), &unfulfilled(&[])));
+        // dropped and responded again by another authority
+        assert!(is_reset(&unfulfilled(&[1, 2]), &unfulfilled(&[3])));
+        assert!(is_reset(&unfulfilled(&[1, 2]), &unfulfilled(&[2, 3])));
+    }
+
+    #[test]
+    fn reset_responded_again() {
+        // a reset is not observable if the same authorities have responded again
+        // since the previous poll
+        assert!(!is_reset(&unfulfilled(&[1, 2]), &unfulfilled(&[2, 1])));
+    }
+}
@@ -0,0 +1,123 @@
+//! Wallet loading (requires the `wallet` feature).
+//!
+//! Supports `forc-wallet` keystores, mnemonic phrases and raw private keys.
+//!
+//! ```no_run
+//! # use fuels::prelude::*;
+//! # use orao_fuel_vrf::wallet::Mnemonic;
+//! # tokio_test::block_on(async {
+//! # let provider: Provider = panic!();
+//! let path = orao_fuel_vrf::wallet::default_keystore_path().expect("home directory");
+//! let mnemonic = Mnemonic::from_keystore(path, "password")?;
+//! for (index, address) in mnemonic.accounts(0..3)? {
+//!     println!("{index}: {address}");
+//! }
+//! let wallet = mnemonic.wallet(0, provider)?;
+//! # orao_fuel_vrf::Result::Ok(()) });
+//! ```
+
+use std::{
+    env, fmt, io,
+    ops::Range,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use fuels::{crypto::SecretKey, prelude::*};
+
+use crate::Result;
+
+/// Environment variable [`from_env`] reads the private key from by default.
+pub const PRIVATE_KEY_ENV: &str = "FUEL_PRIVATE_KEY";
+
+/// Returns `forc-wallet` derivation path of the account with the given index.
+pub fn derivation_path(account_index: usize) -> String {
+    format!("m/44'/1179993420'/{account_index}'/0/0")
+}
+
+/// Returns the default `forc-wallet` keystore path (`~/.fuel/wallets/.wallet`).
+pub fn default_keystore_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| Path::new(&home).join(".fuel/wallets/.wallet"))
+}
+
+/// Mnemonic phrase the accounts are derived from.
+#[derive(Clone)]
+pub struct Mnemonic(String);
+
+impl Mnemonic {
+    pub fn new(phrase: impl Into<String>) -> Self {
+        Self(phrase.into())
+    }
+
+    /// Decrypts a `forc-wallet` keystore.
+    pub fn from_keystore(path: impl AsRef<Path>, password: impl AsRef<[u8]>) -> Result<Self> {
+        let phrase = eth_keystore::decrypt_key(path, password)?;
+        let phrase =
+            String::from_utf8(phrase).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(Self(phrase))
+    }
+
+    pub fn phrase(&self) -> &str {
+        &self.0
+    }
+
+    /// Derives the secret key of the account with the given index.
+    pub fn secret_key(&self, account_index: usize) -> Result<SecretKey> {
+        SecretKey::new_from_mnemonic_phrase_with_path(&self.0, &derivation_path(account_index))
+            .map_err(|e| fuels::types::errors::Error::from(e).into())
+    }
+
+    /// Returns the wallet of the account with the given index.
+    pub fn wallet(&self, account_index: usize, provider: Provider) -> Result<WalletUnlocked> {
+        Ok(WalletUnlocked::new_from_private_key(
+            self.secret_key(account_index)?,
+            Some(provider),
+        ))
+    }
+
+    /// Enumerates addresses of the accounts with the given indices.
+    pub fn accounts(&self, indices: Range<usize>) -> Result<Vec<(usize, Bech32Address)>> {
+        indices
+            .map(|index| {
+                let wallet = WalletUnlocked::new_from_private_key(self.secret_key(index)?, None);
+                Ok((index, wallet.address().clone()))
+            })
+            .collect()
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the phrase
+        f.write_str("Mnemonic(..)")
+    }
+}
+
+/// Returns the wallet of the account with the given index from a `forc-wallet` keystore.
+pub fn from_keystore(
+    path: impl AsRef<Path>,
+    password: impl AsRef<[u8]>,
+    account_index: usize,
+    provider: Provider,
+) -> Result<WalletUnlocked> {
+    Mnemonic::from_keystore(path, password)?.wallet(account_index, provider)
+}
+
+/// Returns the wallet of a hex-encoded private key.
+pub fn from_private_key(private_key: &str, provider: Provider) -> Result<WalletUnlocked> {
+    let secret_key =
+        SecretKey::from_str(private_key.trim()).map_err(fuels::types::errors::Error::from)?;
+    Ok(WalletUnlocked::new_from_private_key(
+        secret_key,
+        Some(provider),
+    ))
+}
+
+/// Returns the wallet of a hex-encoded private key stored in the given environment variable.
+///
+/// See [`PRIVATE_KEY_ENV`].
+pub fn from_env(name: &str, provider: Provider) -> Result<WalletUnlocked> {
+    let private_key = env::var(name)
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, format!("{name} is not set")))?;
+    from_private_key(&private_key, provider)
+}